@@ -0,0 +1,51 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use rolling_buffer::buffer::buffer::RollingBuffer;
+
+const LEN: usize = 1 << 20;
+
+/// A full, wrapped 1M-element buffer, so reads go through both runs.
+fn wrapped() -> RollingBuffer<f64> {
+    let mut buffer = RollingBuffer::new(LEN);
+    buffer.extend((0..LEN + LEN / 3).map(|i| i as f64));
+    buffer
+}
+
+/// Reads the whole buffer element by element versus the bulk copies.
+fn read(c: &mut Criterion) {
+    let buffer = wrapped();
+    let mut out = vec![0.0; LEN];
+    let mut group = c.benchmark_group("read");
+    group.bench_function("iter", |b| b.iter(|| black_box(&buffer).iter().zip(out.iter_mut()).for_each(|(x, o)| *o = *x)));
+    group.bench_function("to_vec", |b| b.iter(|| black_box(&buffer).to_vec()));
+    group.bench_function("copy_to_slice", |b| b.iter(|| black_box(&buffer).copy_to_slice(&mut out)));
+    group.finish();
+}
+
+/// Fills an empty buffer and writes into a full one, pushing element by element versus `extend_from_slice()`.
+fn write(c: &mut Criterion) {
+    let values: Vec<f64> = (0..LEN).map(|i| i as f64).collect();
+    let mut group = c.benchmark_group("write");
+    group.bench_function("fill/push", |b| {
+        b.iter(|| {
+            let mut buffer = RollingBuffer::new(LEN);
+            values.iter().for_each(|&x| buffer.push(x));
+            buffer
+        })
+    });
+    group.bench_function("fill/extend_from_slice", |b| {
+        b.iter(|| {
+            let mut buffer = RollingBuffer::new(LEN);
+            buffer.extend_from_slice(&values);
+            buffer
+        })
+    });
+    let mut buffer = wrapped();
+    group.bench_function("full/push", |b| b.iter(|| values.iter().for_each(|&x| buffer.push(x))));
+    group.bench_function("full/extend_from_slice", |b| b.iter(|| buffer.extend_from_slice(black_box(&values))));
+    group.finish();
+}
+
+criterion_group!(benches, read, write);
+criterion_main!(benches);
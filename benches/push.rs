@@ -0,0 +1,44 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rolling_buffer::buffer::buffer::RollingBuffer;
+
+/// Pushes into a power of two size (masked index) and the next size up (modulo index).
+fn push(c: &mut Criterion) {
+    let mut group = c.benchmark_group("push");
+    for size in [1024, 1025] {
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, &size| {
+            let mut buffer = RollingBuffer::<u64>::new(size);
+            let mut value = 0;
+            b.iter(|| {
+                for _ in 0..10_000 {
+                    buffer.push(black_box(value));
+                    value += 1;
+                }
+                black_box(buffer.last());
+            });
+        });
+    }
+    group.finish();
+}
+
+fn get(c: &mut Criterion) {
+    let mut group = c.benchmark_group("get");
+    for size in [1024, 1025] {
+        let mut buffer = RollingBuffer::<u64>::new(size);
+        buffer.extend(0..10_000);
+        group.bench_with_input(BenchmarkId::from_parameter(size), &buffer, |b, buffer| {
+            b.iter(|| {
+                let mut sum = 0;
+                for i in buffer.live_range() {
+                    sum += buffer.get(black_box(i)).copied().unwrap_or(0);
+                }
+                sum
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, push, get);
+criterion_main!(benches);
@@ -0,0 +1,34 @@
+//! Uses rolling-buffer from a `#![no_std]` crate with only `alloc`, so that
+//! `cargo build -p no-std-check --target thumbv7em-none-eabihf` fails as soon as the crate depends on `std`.
+//! Build it on its own: as part of the workspace the `std` feature gets unified back in.
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use rolling_buffer::buffer::array::RollingArray;
+use rolling_buffer::buffer::buffer::RollingBuffer;
+use rolling_buffer::buffer::traits::{RollingRead, RollingWrite};
+
+/// Pushes a sensor sample into the heap history and returns the mean of the kept samples.
+pub fn smooth(history: &mut RollingBuffer<f32>, sample: f32) -> f32 {
+    history.push(sample);
+    history.iter().sum::<f32>() / history.len() as f32
+}
+
+/// Same as `smooth()`, with the history stored inline.
+pub fn smooth_inline(history: &mut RollingArray<f32, 8>, sample: f32) -> f32 {
+    history.push(sample);
+    history.iter().sum::<f32>() / history.len() as f32
+}
+
+/// Snapshot of the kept samples, oldest first.
+pub fn snapshot(history: &RollingBuffer<f32>) -> Vec<f32> {
+    history.to_vec()
+}
+
+/// Logs the kept samples over the defmt transport, e.g. RTT.
+pub fn log_history(history: &RollingBuffer<u16>) {
+    defmt::info!("{}", history);
+}
@@ -0,0 +1,49 @@
+#[cfg(feature = "allocator_api")]
+pub use alloc::alloc::{Allocator, Global};
+
+/// Stand-in for `core::alloc::Allocator` without the nightly-only `allocator_api` feature.
+/// It names the default allocator parameter of RollingBuffer and is only implemented by `Global`.
+#[cfg(not(feature = "allocator_api"))]
+pub trait Allocator: sealed::Sealed {}
+
+/// Stand-in for `alloc::alloc::Global` without the nightly-only `allocator_api` feature, the global allocator.
+#[cfg(not(feature = "allocator_api"))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Global;
+
+#[cfg(not(feature = "allocator_api"))]
+impl Allocator for Global {}
+
+#[cfg(not(feature = "allocator_api"))]
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for super::Global {}
+}
+
+/// `Vec<T, A>`, naming the allocator parameter needs the `allocator_api` feature.
+#[cfg(feature = "allocator_api")]
+pub(crate) type AllocVec<T, A> = alloc::vec::Vec<T, A>;
+
+/// `Box<[T], A>`, naming the allocator parameter needs the `allocator_api` feature.
+#[cfg(feature = "allocator_api")]
+pub(crate) type AllocSlice<T, A> = alloc::boxed::Box<[T], A>;
+
+/// `Vec<T>` without the `allocator_api` feature, where `A` is always `Global`.
+#[cfg(not(feature = "allocator_api"))]
+pub(crate) type AllocVec<T, A> = <alloc::vec::Vec<T> as InGlobal<A>>::Type;
+
+/// `Box<[T]>` without the `allocator_api` feature, where `A` is always `Global`.
+#[cfg(not(feature = "allocator_api"))]
+pub(crate) type AllocSlice<T, A> = <alloc::boxed::Box<[T]> as InGlobal<A>>::Type;
+
+/// Lets `AllocVec` and `AllocSlice` take the allocator parameter without using it, which a type alias can't do directly.
+#[cfg(not(feature = "allocator_api"))]
+pub(crate) trait InGlobal<A> {
+    type Type;
+}
+
+#[cfg(not(feature = "allocator_api"))]
+impl<T, A> InGlobal<A> for T {
+    type Type = T;
+}
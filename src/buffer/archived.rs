@@ -0,0 +1,165 @@
+use alloc::vec::Vec;
+use core::slice;
+
+use rkyv::bytecheck::CheckBytes;
+use rkyv::munge::munge;
+use rkyv::option::ArchivedOption;
+use rkyv::primitive::{ArchivedU64, ArchivedUsize};
+use rkyv::rancor::{Fallible, Source};
+use rkyv::ser::{Allocator, Writer};
+use rkyv::vec::{ArchivedVec, VecResolver};
+use rkyv::{Archive, Deserialize, Place, Portable, Serialize, SerializeUnsized};
+
+use super::buffer::RollingBuffer;
+use super::error::RawPartsError;
+
+/// Archived form of a RollingBuffer, read straight from the bytes after `rkyv::access()` validated them.
+/// It stores the live elements in logical order as one contiguous run, so `as_slice()` gives
+/// the logical contents without deserializing anything. Like the serde format it keeps `size`,
+/// `count`, whether evictions are tracked and `last_removed`, but not the physical rotation.
+///
+/// ['size']: size is the maximum number of elements of the archived buffer, 0 for an unbounded one
+/// ['count']: count is the number of pushes, logical indices are count truncated to usize
+/// ['track_removed']: track_removed is false for a buffer that doesn't keep evicted elements
+/// ['last_removed']: last_removed is the last element that was evicted by a push
+/// ['data']: data holds the live elements, oldest first
+#[derive(Portable, CheckBytes)]
+#[rkyv(crate = rkyv)]
+#[bytecheck(crate = rkyv::bytecheck)]
+#[repr(C)]
+pub struct ArchivedRollingBuffer<T> {
+    size: ArchivedUsize,
+    count: ArchivedU64,
+    track_removed: bool,
+    last_removed: ArchivedOption<T>,
+    data: ArchivedVec<T>,
+}
+
+impl<T> ArchivedRollingBuffer<T> {
+    /// The live elements, oldest first.
+    pub fn as_slice(&self) -> &[T] {
+        self.data.as_slice()
+    }
+
+    /// Returns an iterator over the live elements, oldest first.
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    /// Get the element at the given logical index, None for indices that were already evicted or not pushed yet.
+    pub fn get(&self, i: usize) -> Option<&T> {
+        let start = self.count().wrapping_sub(self.len());
+        self.data.get(i.wrapping_sub(start))
+    }
+
+    /// Returns the number of live elements.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns true if there are no live elements.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the maximum number of elements of the archived buffer, 0 for an unbounded one.
+    pub fn size(&self) -> usize {
+        self.size.to_native() as usize
+    }
+
+    /// Returns the number of pushes, truncated to usize.
+    pub fn count(&self) -> usize {
+        self.total_pushed() as usize
+    }
+
+    /// Same as `count()`, as a 64-bit number that doesn't wrap on 32-bit targets.
+    pub fn total_pushed(&self) -> u64 {
+        self.count.to_native()
+    }
+
+    /// Returns the last element evicted by a push.
+    pub fn last_removed(&self) -> Option<&T> {
+        self.last_removed.as_ref()
+    }
+
+    /// Whether the archived buffer kept evicted elements in `last_removed`.
+    pub fn tracks_removed(&self) -> bool {
+        self.track_removed
+    }
+}
+
+/// Resolver for an `ArchivedRollingBuffer`, holding the positions of the out-of-line data.
+pub struct RollingBufferResolver<R> {
+    last_removed: Option<R>,
+    data: VecResolver,
+}
+
+impl<T> Archive for RollingBuffer<T>
+where
+    T: Archive,
+{
+    type Archived = ArchivedRollingBuffer<T::Archived>;
+    type Resolver = RollingBufferResolver<T::Resolver>;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        munge!(let ArchivedRollingBuffer { size, count, track_removed, last_removed, data } = out);
+        self.size().resolve((), size);
+        self.total_pushed().resolve((), count);
+        self.tracks_removed().resolve((), track_removed);
+        self.last_removed().resolve(resolver.last_removed, last_removed);
+        ArchivedVec::resolve_from_len(self.len(), resolver.data, data);
+    }
+}
+
+/// Writes the live elements in logical order. Elements that archive as plain bytes (like `f32`)
+/// are copied run by run, the two runs of a wrapped buffer end up next to each other.
+/// Other elements are serialized one by one, since their out-of-line data would otherwise
+/// land between the two runs.
+impl<T, S> Serialize<S> for RollingBuffer<T>
+where
+    T: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        let last_removed = self.last_removed().serialize(serializer)?;
+        let (first, second) = self.as_slices();
+        let data = if second.is_empty() {
+            ArchivedVec::<T::Archived>::serialize_from_slice(first, serializer)?
+        } else if T::COPY_OPTIMIZATION.is_enabled() {
+            let pos = first.serialize_unsized(serializer)?;
+            let next = second.serialize_unsized(serializer)?;
+            debug_assert_eq!(next, pos + size_of_val(first));
+            VecResolver::from_pos(pos)
+        } else {
+            ArchivedVec::<T::Archived>::serialize_from_iter::<T, _, _>(self.iter(), serializer)?
+        };
+        Ok(RollingBufferResolver { last_removed, data })
+    }
+}
+
+/// Restores a buffer the same way the serde `Deserialize` impl does, so that it returns the same `get()` results
+/// and evicts the same element on the next push. Fails with a `RawPartsError` if a fixed size buffer
+/// holds more than `size` elements or `count` is below their number.
+impl<T, D> Deserialize<RollingBuffer<T>, D> for ArchivedRollingBuffer<T::Archived>
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<RollingBuffer<T>, D::Error> {
+        let (size, count, len) = (self.size(), self.total_pushed(), self.len());
+        if size > 0 && len > size {
+            return Err(Source::new(RawPartsError::LengthMismatch { size, len }));
+        }
+        if count < len as u64 {
+            // below len, so count fits into a usize
+            return Err(Source::new(RawPartsError::CountMismatch { count: count as usize, len }));
+        }
+        let data = self.iter().map(|value| value.deserialize(deserializer)).collect::<Result<Vec<T>, _>>()?;
+        let last_removed = self.last_removed().map(|value| value.deserialize(deserializer)).transpose()?;
+        let mut buffer = RollingBuffer::from_newest(size, data, count, last_removed);
+        buffer.set_track_removed(self.tracks_removed());
+        Ok(buffer)
+    }
+}
@@ -0,0 +1,36 @@
+use core::array;
+
+use super::storage::{RollingStorage, Storage};
+
+/// RollingArray is a fixed size rolling buffer stored inline in a `[T; N]`, so it never touches the heap.
+/// It has the same logical indices, `count` and `last_removed` as a RollingBuffer of size `N`.
+/// Unused slots hold `T::default()`, and `N` must not be 0 since there is no unbounded mode.
+pub type RollingArray<T, const N: usize> = RollingStorage<T, [T; N]>;
+
+impl<T: Default, const N: usize> Storage<T> for [T; N] {
+    const SIZE: usize = N;
+    const NAME: &'static str = "RollingArray";
+
+    /// Every slot is set to `T::default()`.
+    fn empty() -> Self {
+        const { assert!(N > 0, "a RollingArray needs at least one slot") };
+        array::from_fn(|_| T::default())
+    }
+
+    /// Overwrites the `T::default()` placeholder.
+    fn store(&mut self, index: usize, value: T) {
+        self[index] = value;
+    }
+
+    /// Resets every slot to `T::default()`.
+    fn reset(&mut self) {
+        self.iter_mut().for_each(|slot| *slot = T::default());
+    }
+}
+
+impl<T: Default, const N: usize> RollingArray<T, N> {
+    /// Returns the slots as they are stored, same as `RollingBuffer::as_raw_slice()`.
+    pub fn as_raw_slice(&self) -> &[T] {
+        self.raw()
+    }
+}
@@ -1,66 +1,987 @@
-use std::cmp::min;
+use alloc::collections::{TryReserveError, VecDeque};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::{min, Ordering};
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Bound, Index, IndexMut, Range, RangeBounds, Sub};
+
+use super::allocator::{Allocator, Global};
+use super::builder::RollingBufferBuilder;
+use super::error::{GetError, RawPartsError};
+use super::iter::{
+    self, Chunks, ChunksExact, Deltas, Drain, IntoIter, Iter, IterFrom, IterIndexed, IterMut, MapPairs, RangeIter,
+    Windows, ZipMap,
+};
+use super::ring;
+use super::slots::Slots;
+use super::stats::{self, Sample};
+use super::traits::{RollingRead, RollingWrite};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// Mask replacing `% size` in the index computation, for sizes that are a power of two.
+fn mask_for(size: usize) -> Option<usize> {
+    size.is_power_of_two().then(|| size - 1)
+}
 
-use super::traits::Rolling;
 /// RollingBuffer is a fixed size heap buffer that will override the beginning of the buffer when it is full
 /// RollingBuffer is a very simple Vec wrapper that only uses safe code.
 /// 
 /// ['size']: size is the maximum number of elements that the buffer can hold
-/// ['vec']: vec is the underlying Vec that stores the elements of the buffer
+/// ['slots']: slots stores the elements of the buffer in `A`, in a Vec while the buffer fills up and in a boxed slice
+///     of exactly `size` elements once it's full
 /// ['last_removed']: last_removed is the last element that was removed from the buffer
-/// ['count']: count is the number of elements in the buffer as if the buffer was Vec
-#[derive(Debug, Clone, Default)]
-pub struct RollingBuffer<T>
-where
-    T: Clone
-{
+/// ['count']: count is the number of elements in the buffer as if the buffer was Vec, it only numbers the logical indices
+///     and is 64-bit so it doesn't wrap on 32-bit targets. Logical indices are count truncated to usize.
+/// ['discard_removed']: discard_removed disables keeping the evicted element in last_removed
+/// ['wipe_removed']: wipe_removed zeroizes an evicted element in its slot before it is overwritten, see `zeroize_removed()`
+/// ['head']: head is the physical index of the oldest live element, the k-th live element is stored at (head + k) % size.
+///     A buffer that isn't full only stores its live elements, oldest first, so head is 0 until the ring is full
+///     and the number of live elements is always the number of stored ones.
+/// ['mask']: mask is `size - 1` when size is a power of two, letting the index computation use `&` instead of a branch
+/// ['alloc']: alloc is the allocator when the `allocator_api` feature is off, since the slots can only hold it with the feature
+#[derive(Clone)]
+pub struct RollingBuffer<T, A: Allocator = Global> {
     size: usize,
-    vec: Vec<T>,
+    slots: Slots<T, A>,
     last_removed: Option<T>,
-    count: usize,
+    count: u64,
+    discard_removed: bool,
+    #[cfg(feature = "zeroize")]
+    wipe_removed: Option<fn(&mut T)>,
+    head: usize,
+    mask: Option<usize>,
+    #[cfg(not(feature = "allocator_api"))]
+    alloc: A,
 }
 
-
-impl<T> Rolling<T> for RollingBuffer<T> 
-where
-    T: Clone + Default
-{
+impl<T> RollingBuffer<T> {
     /// Creates a new RollingBuffer with the given size and initial value (aka none)
+    /// Nothing is allocated up front, the underlying Vec grows on push until it holds `size` slots.
     /// If the size is 0, the buffer will behave as a normal Vec
-    fn new(size: usize) -> Self {
+    pub fn new(size: usize) -> Self {
+        Self::new_in(size, Global)
+    }
+
+    /// Creates a new RollingBuffer of the given size that is full from the start,
+    /// every slot holding a clone of `value` and counting as a live element (`count == size`).
+    /// Pushing afterwards evicts the seeded values as usual.
+    pub fn new_filled(size: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
         Self {
             size,
-            vec: if size > 0 {
-                vec![T::default(); size]
-            } else {
-                Vec::new()
-            },
+            slots: Slots::new(vec![value; size], size),
+            last_removed: None,
+            count: size as u64,
+            discard_removed: false,
+            #[cfg(feature = "zeroize")]
+            wipe_removed: None,
+            head: 0,
+            mask: mask_for(size),
+            #[cfg(not(feature = "allocator_api"))]
+            alloc: Global,
+        }
+    }
+
+    /// Same as `new()`, but reserves all `size` slots up front and returns an error instead of
+    /// aborting when the allocation fails, e.g. for an oversized window requested by a user.
+    /// Pushing never reallocates afterwards.
+    pub fn try_new(size: usize) -> Result<Self, TryReserveError> {
+        let mut vec = Vec::new();
+        vec.try_reserve_exact(size)?;
+        Ok(Self {
+            size,
+            slots: Slots::new(vec, size),
             last_removed: None,
             count: 0,
+            discard_removed: false,
+            #[cfg(feature = "zeroize")]
+            wipe_removed: None,
+            head: 0,
+            mask: mask_for(size),
+            #[cfg(not(feature = "allocator_api"))]
+            alloc: Global,
+        })
+    }
+
+    /// Creates a new RollingBuffer of the given size with every slot initialized by `f(index)`.
+    /// Like `new_filled()` the buffer is full from the start, `f(0)` being the oldest element.
+    pub fn new_with(size: usize, f: impl FnMut(usize) -> T) -> Self {
+        Self {
+            size,
+            slots: Slots::new((0..size).map(f).collect(), size),
+            last_removed: None,
+            count: size as u64,
+            discard_removed: false,
+            #[cfg(feature = "zeroize")]
+            wipe_removed: None,
+            head: 0,
+            mask: mask_for(size),
+            #[cfg(not(feature = "allocator_api"))]
+            alloc: Global,
         }
     }
 
-    
-    /// Adds an element to the buffer, overriding the beginning of the buffer when it is full
-    /// Here using "safe code", but it is essentially unsafe ptr::write()
-    fn push(&mut self, value: T) {
-        if self.size > 0 {
-            let index = self.count as usize % self.size;
-            self.last_removed = Some(std::mem::replace(&mut self.vec[index], value));
+    /// Decomposes the buffer into `(vec, size, count, last_removed)`.
+    /// The vec is laid out the way `count` pushes into a fresh buffer would have left it,
+    /// so the parts can be turned back into a buffer with `from_raw_parts()`.
+    /// A buffer that isn't full, because it hasn't filled up yet or elements were popped, only stores
+    /// its live elements, oldest first, and returns them as they are.
+    pub fn into_raw_parts(mut self) -> (Vec<T>, usize, usize, Option<T>) {
+        if self.is_full() {
+            // move the oldest live element to the slot `count - size` pushes into a fresh buffer would have used
+            let oldest = (self.count.wrapping_sub(self.size as u64) % self.size as u64) as usize;
+            self.slots.rotate_left((self.head + self.size - oldest) % self.size);
+        }
+        (self.slots.into_vec(), self.size, self.count as usize, self.last_removed)
+    }
+
+    /// Consumes the buffer and returns its elements in logical order without cloning them.
+    /// The slots are rotated in place and their allocation is reused.
+    pub fn into_vec(mut self) -> Vec<T> {
+        self.make_contiguous();
+        self.slots.into_vec()
+    }
+
+    /// Rebuilds a buffer from the parts returned by `into_raw_parts()`.
+    /// The parts are validated first: a fixed size buffer can't hold more than `size` slots,
+    /// and no buffer can have fewer pushes than stored elements (`count >= vec.len()`) since every one of them is live.
+    /// A vec shorter than `size` is taken as the live elements, oldest first.
+    pub fn from_raw_parts(
+        vec: Vec<T>,
+        size: usize,
+        count: usize,
+        last_removed: Option<T>,
+    ) -> Result<Self, RawPartsError> {
+        if size > 0 && vec.len() > size {
+            return Err(RawPartsError::LengthMismatch { size, len: vec.len() });
+        }
+        if count < vec.len() {
+            return Err(RawPartsError::CountMismatch { count, len: vec.len() });
+        }
+        if size > 0 && vec.len() < size {
+            return Ok(Self::from_newest(size, vec, count as u64, last_removed));
+        }
+        Ok(Self {
+            size,
+            slots: Slots::new(vec, size),
+            last_removed,
+            count: count as u64,
+            discard_removed: false,
+            #[cfg(feature = "zeroize")]
+            wipe_removed: None,
+            head: if size > 0 { count % size } else { 0 },
+            mask: mask_for(size),
+            #[cfg(not(feature = "allocator_api"))]
+            alloc: Global,
+        })
+    }
+
+    /// Returns a builder for configuring a new RollingBuffer.
+    pub fn builder() -> RollingBufferBuilder<T> {
+        RollingBufferBuilder::new()
+    }
+
+    /// Lays out the newest live values (in logical order) the way `count` pushes would have.
+    /// A full window is rotated into place, fewer values and those of an unbounded buffer are stored as they are,
+    /// oldest first. `count` stays 64-bit so a deserialized buffer doesn't lose pushes on 32-bit targets.
+    pub(crate) fn from_newest(size: usize, mut newest: Vec<T>, count: u64, last_removed: Option<T>) -> Self {
+        let len = newest.len();
+        let mut head = 0;
+        if size > 0 && len == size {
+            head = (count % size as u64) as usize;
+            newest.rotate_right(head);
+        }
+        Self {
+            size,
+            slots: Slots::new(newest, size),
+            last_removed,
+            count,
+            discard_removed: false,
+            #[cfg(feature = "zeroize")]
+            wipe_removed: None,
+            head,
+            mask: mask_for(size),
+            #[cfg(not(feature = "allocator_api"))]
+            alloc: Global,
+        }
+    }
+}
+
+impl<T> Default for RollingBuffer<T> {
+    /// Same as `new(0)`, an empty unbounded buffer.
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl<T, A: Allocator> RollingBuffer<T, A> {
+    /// Same as `new()`, but the underlying Vec is allocated in `alloc`, e.g. an arena or a counting allocator.
+    /// Slots are still allocated on push. Allocators other than `Global` need the nightly-only
+    /// `allocator_api` feature.
+    pub fn new_in(size: usize, alloc: A) -> Self {
+        Self {
+            size,
+            #[cfg(feature = "allocator_api")]
+            slots: Slots::Growing(Vec::new_in(alloc)),
+            #[cfg(not(feature = "allocator_api"))]
+            slots: Slots::Growing(Vec::new()),
+            last_removed: None,
+            count: 0,
+            discard_removed: false,
+            #[cfg(feature = "zeroize")]
+            wipe_removed: None,
+            head: 0,
+            mask: mask_for(size),
+            #[cfg(not(feature = "allocator_api"))]
+            alloc,
+        }
+    }
+
+    /// Returns the allocator the buffer stores its elements in.
+    #[cfg(feature = "allocator_api")]
+    pub fn allocator(&self) -> &A {
+        self.slots.allocator()
+    }
+
+    /// Returns the allocator the buffer stores its elements in.
+    #[cfg(not(feature = "allocator_api"))]
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
+    /// Same as `to_vec()`, but the new Vec is allocated in `alloc`.
+    #[cfg(feature = "allocator_api")]
+    pub fn to_vec_in<B: Allocator>(&self, alloc: B) -> Vec<T, B>
+    where
+        T: Clone,
+    {
+        let (first, second) = self.as_slices();
+        let mut vec = Vec::with_capacity_in(first.len() + second.len(), alloc);
+        vec.extend_from_slice(first);
+        vec.extend_from_slice(second);
+        vec
+    }
+
+    /// Reinitializes the buffer in place, as if it was created with `new_with(size, f)`.
+    /// For an unbounded buffer the stored elements are overwritten by `f(index)`.
+    pub fn fill_with(&mut self, f: impl FnMut(usize) -> T) {
+        let len = if self.size == 0 { self.slots.len() } else { self.size };
+        let vec = self.slots.vec();
+        vec.clear();
+        vec.extend((0..len).map(f));
+        self.slots.settle(self.size);
+        self.count = len as u64;
+        self.last_removed = None;
+        self.head = 0;
+    }
+
+    /// Whether evicted elements are kept in `last_removed()`, false for a buffer built with `track_removed(false)`.
+    pub fn tracks_removed(&self) -> bool {
+        !self.discard_removed
+    }
+
+    /// Sets whether the evicted element is kept in `last_removed` or dropped right away.
+    pub(crate) fn set_track_removed(&mut self, track_removed: bool) {
+        self.discard_removed = !track_removed;
+        if !track_removed {
+            self.last_removed = None;
+        }
+    }
+
+    /// Returns the slots as they are stored inside the RollingBuffer.
+    /// Slots are allocated on push and given back by `pop()` and the like, so a buffer that isn't full
+    /// only holds its live elements, oldest first, and the slice is shorter than `size()`.
+    /// A full buffer stores its slots in a boxed slice rather than a Vec, so this is a slice and not a `&Vec<T>`.
+    pub fn raw(&self) -> &[T] {
+        &self.slots
+    }
+
+    /// Same as `raw()`.
+    pub fn as_raw_slice(&self) -> &[T] {
+        &self.slots
+    }
+
+    /// Capacity of the underlying Vec. The Vec of a fixed size buffer only grows on push until it holds
+    /// `size` slots, then they are moved into a boxed slice and the capacity is exactly `size`.
+    pub fn capacity(&self) -> usize {
+        self.slots.capacity()
+    }
+
+    /// Reserves room for at least `additional` more elements of an unbounded buffer.
+    /// Does nothing for a fixed size buffer.
+    pub fn reserve(&mut self, additional: usize) {
+        if self.size == 0 {
+            self.slots.vec().reserve(additional);
+        }
+    }
+
+    /// Releases unused capacity of an unbounded buffer, e.g. after a `clear()` or `drain()`.
+    /// Does nothing for a fixed size buffer.
+    pub fn shrink_to_fit(&mut self) {
+        if self.size == 0 {
+            self.slots.vec().shrink_to_fit();
+        }
+    }
+
+    /// Returns the live elements as at most two contiguous runs of the underlying Vec, like `VecDeque::as_slices`.
+    /// Concatenating the first and the second slice gives the logical order: the first one runs from the oldest
+    /// element to the physical end (or to the newest one if the buffer hasn't wrapped), the second one is
+    /// the wrapped prefix and may be empty. The unused slots of an underfilled buffer are not included.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        ring::as_slices(&self.slots, self.head, self.live_len(), self.size)
+    }
+
+    /// Mutable version of `as_slices()`.
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let len = self.live_len();
+        ring::as_mut_slices(&mut self.slots, self.head, len, self.size)
+    }
+
+    /// Overwrites every live element with a clone of `value`; `count`, `len` and logical indices don't change.
+    /// To reinitialize the whole buffer from a closure see `fill_with()`.
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        let (first, second) = self.as_mut_slices();
+        first.fill(value.clone());
+        second.fill(value);
+    }
+
+    /// Rotates the underlying Vec so that its physical order equals the logical order,
+    /// and returns the live elements as one contiguous slice.
+    /// `count`, `len()`, `last_removed` and logical indices are not affected.
+    /// Cheap when the buffer hasn't wrapped, since nothing needs to move then.
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        if self.size == 0 {
+            return &mut self.slots[..];
+        }
+        if self.head != 0 {
+            self.slots.rotate_left(self.head);
+            self.head = 0;
+        }
+        &mut self.slots[..]
+    }
+
+    /// Reverses the order of the live elements in place, the newest becomes the oldest.
+    /// The next push evicts what is now logically oldest (the previously newest element).
+    pub fn reverse(&mut self) {
+        self.make_contiguous().reverse();
+    }
+
+    /// Rotates the logical view `n` places to the left, so `first()` becomes what was at position `n`.
+    /// `n` is taken modulo `len()`. A full buffer only moves its head, an underfilled one moves elements.
+    /// The next push into a full buffer evicts the new `first()`.
+    pub fn rotate_left(&mut self, n: usize) {
+        let len = self.live_len();
+        if len == 0 {
+            return;
+        }
+        if self.size > 0 && len == self.size {
+            self.head = (self.head + n % self.size) % self.size;
+        } else {
+            self.make_contiguous().rotate_left(n % len);
+        }
+    }
+
+    /// Rotates the logical view `n` places to the right, so `first()` becomes what was the `n`-th newest element.
+    /// Same rules as `rotate_left()`.
+    pub fn rotate_right(&mut self, n: usize) {
+        let len = self.live_len();
+        if len > 0 {
+            self.rotate_left(len - n % len);
+        }
+    }
+
+    /// Sorts the live elements in place with a stable sort, after making the storage contiguous.
+    /// This destroys the logical insertion order: the smallest element becomes the oldest,
+    /// so it is the one evicted by the next push into a full buffer.
+    pub fn sort_by(&mut self, compare: impl FnMut(&T, &T) -> Ordering) {
+        self.make_contiguous().sort_by(compare);
+    }
+
+    /// Same as `sort_by()` but unstable, which avoids the allocation of the stable sort.
+    pub fn sort_unstable_by(&mut self, compare: impl FnMut(&T, &T) -> Ordering) {
+        self.make_contiguous().sort_unstable_by(compare);
+    }
+
+    /// Same as `sort_by()`, comparing the keys extracted by `f`.
+    pub fn sort_by_key<K: Ord>(&mut self, f: impl FnMut(&T) -> K) {
+        self.make_contiguous().sort_by_key(f);
+    }
+
+    /// Physical index of the slot holding the `k`-th live element (0 being the oldest) of a fixed size buffer.
+    /// `k` can be at most `size`, which is where the next push goes once the buffer is full.
+    fn slot(&self, k: usize) -> usize {
+        match self.mask {
+            Some(mask) => (self.head + k) & mask,
+            None => ring::slot(self.head, k, self.size),
+        }
+    }
+
+    /// Maps a logical index (as accepted by `get()`) to the physical index in the vec,
+    /// or None if the index was already evicted or hasn't been pushed yet.
+    pub(crate) fn logical_to_physical(&self, i: usize) -> Option<usize> {
+        self.locate(i).ok()
+    }
+
+    /// Position of logical index `i` in the live window, see `ring::window_offset()`.
+    fn window_offset(&self, i: usize) -> Result<usize, Ordering> {
+        ring::window_offset(self.count, self.live_len(), i)
+    }
+
+    /// Same as `window_offset()`, clamped to `0..=len()`.
+    fn clamped_offset(&self, i: usize) -> usize {
+        match self.window_offset(i) {
+            Ok(k) => k,
+            Err(Ordering::Less) => 0,
+            Err(_) => self.live_len(),
+        }
+    }
+
+    /// Same as `logical_to_physical()`, telling why the index is outside of the live window.
+    fn locate(&self, i: usize) -> Result<usize, GetError> {
+        let live = self.live_range();
+        match self.window_offset(i) {
+            Ok(k) if self.size == 0 => Ok(k),
+            Ok(k) => Ok(self.slot(k)),
+            Err(Ordering::Less) => Err(GetError::Evicted { index: i, oldest_live: live.start }),
+            Err(_) => Err(GetError::NotYetPushed { index: i, next: live.end }),
+        }
+    }
+
+    /// Panics with a message telling whether the logical index `i` was evicted or not pushed yet.
+    fn out_of_window(&self, i: usize) -> ! {
+        let live = self.live_range();
+        if self.window_offset(i) == Err(Ordering::Less) {
+            panic!("logical index {i} evicted; live range is {live:?}")
         } else {
-            self.vec.push(value);
+            panic!("logical index {i} not yet pushed; live range is {live:?}")
         }
-        self.count += 1;
     }
 
-    
+    /// Returns the logical index of the oldest live element, or None if the buffer is empty.
+    pub fn oldest_index(&self) -> Option<usize> {
+        if self.live_len() == 0 {
+            None
+        } else {
+            Some(self.live_range().start)
+        }
+    }
+
+    /// Returns the half-open range of logical indices that `get()` can currently return elements for.
+    /// Its length equals `len()`, unless the logical indices wrapped around `usize::MAX` within the window
+    /// (only reachable on 32-bit targets), in which case `start > end`.
+    pub fn live_range(&self) -> Range<usize> {
+        let end = self.count as usize;
+        end.wrapping_sub(self.live_len())..end
+    }
+
+    /// Same as `get()`, but tells whether a missing element was evicted or not pushed yet.
+    pub fn get_checked(&self, i: usize) -> Result<&T, GetError> {
+        let index = self.locate(i)?;
+        Ok(&self.slots[index])
+    }
+
+    /// Swaps the elements at logical indices `i` and `j` without cloning.
+    /// Panics like indexing does if either index is outside of the live window.
+    pub fn swap(&mut self, i: usize, j: usize) {
+        let a = self.locate(i).unwrap_or_else(|_| self.out_of_window(i));
+        let b = self.locate(j).unwrap_or_else(|_| self.out_of_window(j));
+        self.slots.swap(a, b);
+    }
+
+    /// Number of live elements, same as `len()`.
+    fn live_len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Same as `to_vec()`, but writes the elements into `out`, reusing its allocation.
+    /// `out` is cleared first, then the at most two contiguous runs are appended in logical order.
+    pub fn to_vec_into(&self, out: &mut Vec<T>)
+    where
+        T: Clone,
+    {
+        let (first, second) = self.as_slices();
+        out.clear();
+        out.reserve(first.len() + second.len());
+        out.extend_from_slice(first);
+        out.extend_from_slice(second);
+    }
+
+    /// Copies the live elements into `out` in logical order, as at most two `copy_from_slice()` calls.
+    /// Panics if `out.len()` is not `len()`.
+    pub fn copy_to_slice(&self, out: &mut [T])
+    where
+        T: Copy,
+    {
+        let (first, second) = self.as_slices();
+        assert_eq!(out.len(), first.len() + second.len(), "destination length doesn't match the buffer length");
+        let (out_first, out_second) = out.split_at_mut(first.len());
+        out_first.copy_from_slice(first);
+        out_second.copy_from_slice(second);
+    }
+
+    /// Returns an iterator over `(logical index, &element)` pairs in logical order.
+    /// The logical index is the one accepted by `get()`, so for a wrapped buffer it starts at `count - len`.
+    pub fn iter_indexed(&self) -> IterIndexed<'_, T> {
+        let (first, second) = self.as_slices();
+        IterIndexed::new(self.live_range().start, Iter::new(first, second))
+    }
+
+    /// Returns an iterator over the elements from logical index `i` up to the newest one.
+    /// If `i` was already evicted, iteration starts at the oldest retained element and
+    /// `IterFrom::skipped()` tells how many elements were lost.
+    ///
+    /// Useful as a catch-up log: remember `count()` and later call `iter_from()` with it.
+    pub fn iter_from(&self, i: usize) -> IterFrom<'_, T> {
+        let (first, second) = self.as_slices();
+        let live = self.live_range();
+        let (start, skipped) = match self.window_offset(i) {
+            Ok(k) => (k, 0),
+            Err(Ordering::Less) => (0, live.start.wrapping_sub(i)),
+            Err(_) => (self.live_len(), 0),
+        };
+        IterFrom::new(
+            Iter::range(first, second, start, self.live_len()),
+            live.start.wrapping_add(start),
+            skipped,
+        )
+    }
+
+    /// Returns an iterator over the elements whose logical indices fall into `range`.
+    /// The parts of the range outside of the live window are silently clipped, the actual
+    /// first index is available through `RangeIter::start_index()`. Empty or reversed ranges yield nothing.
+    pub fn range(&self, range: impl RangeBounds<usize>) -> RangeIter<'_, T> {
+        let len = self.live_len();
+        let start = match range.start_bound() {
+            Bound::Included(&start) => self.clamped_offset(start),
+            Bound::Excluded(&start) => start.checked_add(1).map_or(len, |start| self.clamped_offset(start)),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.checked_add(1).map_or(len, |end| self.clamped_offset(end)),
+            Bound::Excluded(&end) => self.clamped_offset(end),
+            Bound::Unbounded => len,
+        };
+        let (first, second) = self.as_slices();
+        let end = end.max(start);
+        RangeIter::new(Iter::range(first, second, start, end), self.live_range().start.wrapping_add(start))
+    }
+
+    /// Returns an iterator over all overlapping windows of `k` consecutive elements, like `slice::windows`.
+    /// Each window is yielded as an `Iter`, so windows crossing the wrap point don't allocate.
+    /// Yields nothing when the buffer holds fewer than `k` elements.
+    ///
+    /// Panics if `k` is 0.
+    pub fn windows(&self, k: usize) -> Windows<'_, T> {
+        let (first, second) = self.as_slices();
+        Windows::new(first, second, k)
+    }
+
+    /// Returns an iterator over non-overlapping chunks of `k` elements in logical order, like `slice::chunks`.
+    /// The last chunk is shorter if `len()` is not divisible by `k`.
+    ///
+    /// Panics if `k` is 0.
+    pub fn chunks(&self, k: usize) -> Chunks<'_, T> {
+        let (first, second) = self.as_slices();
+        Chunks::new(first, second, k)
+    }
+
+    /// Same as `chunks()`, but every chunk has exactly `k` elements.
+    /// The leftover elements can be retrieved with `ChunksExact::remainder()`.
+    ///
+    /// Panics if `k` is 0.
+    pub fn chunks_exact(&self, k: usize) -> ChunksExact<'_, T> {
+        let (first, second) = self.as_slices();
+        ChunksExact::new(first, second, k)
+    }
+
+    /// Returns an iterator over the oldest `n` live elements in logical order.
+    /// If fewer than `n` elements are stored, all of them are returned.
+    pub fn first_n(&self, n: usize) -> Iter<'_, T> {
+        let (first, second) = self.as_slices();
+        let len = first.len() + second.len();
+        Iter::range(first, second, 0, min(n, len))
+    }
+
+    /// Returns an iterator over the newest `n` elements in logical order.
+    /// If fewer than `n` elements are stored, all of them are returned.
+    pub fn last_n(&self, n: usize) -> Iter<'_, T> {
+        let (first, second) = self.as_slices();
+        let len = first.len() + second.len();
+        Iter::range(first, second, len - min(n, len), len)
+    }
+
+    /// Returns an iterator applying `f(previous, next)` to every pair of consecutive elements in logical order.
+    /// Yields `len() - 1` items, or nothing if fewer than two elements are stored.
+    pub fn map_pairs<F, B>(&self, f: F) -> MapPairs<'_, T, F>
+    where
+        F: FnMut(&T, &T) -> B,
+    {
+        let (first, second) = self.as_slices();
+        MapPairs::new(Iter::new(first, second), f)
+    }
+
+    /// Returns an iterator over the differences between consecutive elements (`next - previous`) in logical order.
+    /// Handy for turning cumulative counters into rates.
+    pub fn deltas(&self) -> Deltas<'_, T>
+    where
+        T: Sub<Output = T> + Clone,
+    {
+        let (first, second) = self.as_slices();
+        MapPairs::new(Iter::new(first, second), iter::delta as fn(&T, &T) -> T)
+    }
+
+    /// Returns an iterator applying `f` to the live elements of this buffer and `other` paired up by their
+    /// position from the newest element: the newest with the newest, the one before it with the one before,
+    /// and so on for `min(self.len(), other.len())` pairs, oldest pair first. The counts of the two buffers
+    /// don't matter, so windows of synchronized streams stay aligned on their most recent samples.
+    /// The two buffers may live in different allocators.
+    pub fn zip_map<'a, U, F, B, C: Allocator>(&'a self, other: &'a RollingBuffer<U, C>, f: F) -> ZipMap<'a, T, U, F>
+    where
+        F: FnMut(&'a T, &'a U) -> B,
+    {
+        ZipMap::new(self.iter(), other.iter(), f)
+    }
+
+    /// Dot product of the live elements of this buffer and `other`, aligned on the newest element
+    /// as in `zip_map()`, summed with compensation. 0 if either buffer is empty.
+    pub fn dot<U, C: Allocator>(&self, other: &RollingBuffer<U, C>) -> f64
+    where
+        T: Sample,
+        U: Sample,
+    {
+        stats::compensated_sum(self.zip_map(other, |a, b| a.to_f64() * b.to_f64()))
+    }
+
+    /// Applies the FIR filter `kernel` to the newest elements: `kernel[0] * x[n] + kernel[1] * x[n - 1] + ...`,
+    /// where `x[n]` is the newest element, so `kernel[i]` weighs the element `i` steps back.
+    /// None while fewer than `kernel.len()` elements are live.
+    pub fn apply_kernel(&self, kernel: &[f64]) -> Option<f64>
+    where
+        T: Sample,
+    {
+        self.apply_kernel_at(0, kernel)
+    }
+
+    /// Same as `apply_kernel()` for the filter output `offset` steps back in time, with `x[n]` being
+    /// `get_back(offset)`, so a filtered history is `apply_kernel_at(offset, kernel)` for every `offset`
+    /// from `len() - kernel.len()` down to 0. None if fewer than `offset + kernel.len()` elements are live.
+    pub fn apply_kernel_at(&self, offset: usize, kernel: &[f64]) -> Option<f64>
+    where
+        T: Sample,
+    {
+        let (first, second) = self.as_slices();
+        let end = self.live_len().checked_sub(offset)?;
+        let start = end.checked_sub(kernel.len())?;
+        let tail = Iter::range(first, second, start, end).rev();
+        Some(stats::compensated_sum(tail.zip(kernel).map(|(value, weight)| value.to_f64() * weight)))
+    }
+
+    /// Folds the live elements in logical order into an accumulator, like `Iterator::fold`,
+    /// running over the two contiguous runs one after the other.
+    pub fn fold<B>(&self, init: B, mut f: impl FnMut(B, &T) -> B) -> B {
+        let (first, second) = self.as_slices();
+        let acc = first.iter().fold(init, &mut f);
+        second.iter().fold(acc, f)
+    }
+
+    /// Calls `f` on every live element in logical order, run by run, to modify them in place.
+    pub fn for_each_mut(&mut self, mut f: impl FnMut(&mut T)) {
+        let (first, second) = self.as_mut_slices();
+        first.iter_mut().for_each(&mut f);
+        second.iter_mut().for_each(f);
+    }
+
+    /// Returns true if any live element equals `x`.
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        let (first, second) = self.as_slices();
+        first.contains(x) || second.contains(x)
+    }
+
+    /// Returns the logical index of the oldest live element matching `pred`, the index `get()` takes,
+    /// so for a wrapped buffer it is at least `count - len`.
+    pub fn position(&self, mut pred: impl FnMut(&T) -> bool) -> Option<usize> {
+        let (first, second) = self.as_slices();
+        let k = first.iter().position(&mut pred).or_else(|| Some(first.len() + second.iter().position(pred)?))?;
+        Some(self.live_range().start.wrapping_add(k))
+    }
+
+    /// Same as `position()` for the newest live element matching `pred`.
+    pub fn rposition(&self, mut pred: impl FnMut(&T) -> bool) -> Option<usize> {
+        let (first, second) = self.as_slices();
+        let k = second.iter().rposition(&mut pred).map(|k| first.len() + k).or_else(|| first.iter().rposition(pred))?;
+        Some(self.live_range().start.wrapping_add(k))
+    }
+
+    /// Returns the number of live elements matching `pred`.
+    pub fn count_matching(&self, mut pred: impl FnMut(&T) -> bool) -> usize {
+        let (first, second) = self.as_slices();
+        first.iter().filter(|value| pred(value)).count() + second.iter().filter(|value| pred(value)).count()
+    }
+
+    /// Binary searches the live elements, which must be sorted in logical order, for `x`, like `slice::binary_search`.
+    /// Returns `Ok` with the logical index of a matching element, or `Err` with the logical index `x`
+    /// would have to be inserted at: `count - len` if it's smaller than all elements, `count` if it's larger.
+    pub fn binary_search(&self, x: &T) -> Result<usize, usize>
+    where
+        T: Ord,
+    {
+        self.binary_search_by(|value| value.cmp(x))
+    }
+
+    /// Same as `binary_search()` with a comparator returning the ordering of an element relative to the target.
+    /// Only one of the two runs is searched, picked by comparing the newest element of the first run.
+    pub fn binary_search_by(&self, mut f: impl FnMut(&T) -> Ordering) -> Result<usize, usize> {
+        let (first, second) = self.as_slices();
+        let start = self.live_range().start;
+        let found = match first.last().map(&mut f) {
+            Some(Ordering::Less) => second.binary_search_by(f).map(|k| first.len() + k).map_err(|k| first.len() + k),
+            _ => first.binary_search_by(f),
+        };
+        found.map(|k| start.wrapping_add(k)).map_err(|k| start.wrapping_add(k))
+    }
+
+    /// Returns the logical index of the first live element for which `pred` is false, like `slice::partition_point`,
+    /// assuming it is true for all elements before and false for all after it. `count` if it's true for all of them.
+    pub fn partition_point(&self, mut pred: impl FnMut(&T) -> bool) -> usize {
+        let (first, second) = self.as_slices();
+        let k = match first.last() {
+            Some(last) if pred(last) => first.len() + second.partition_point(pred),
+            _ => first.partition_point(pred),
+        };
+        self.live_range().start.wrapping_add(k)
+    }
+}
+
+impl<T> RollingBuffer<T> {
+    /// Creates a new RollingBuffer of the given size seeded with `values`, as if every value was pushed in order.
+    /// Only the last `size` values are kept, `count` is set to `values.len()` so logical indices line up,
+    /// and `last_removed` is the most recently discarded value (None if nothing was discarded).
+    /// If the size is 0, the buffer will behave as a normal Vec holding all values.
+    pub fn from_vec(size: usize, mut values: Vec<T>) -> Self {
+        let count = values.len();
+        if size == 0 {
+            return Self::from(values);
+        }
+        let discarded = count.saturating_sub(size);
+        let last_removed = values.drain(..discarded).next_back();
+        Self::from_newest(size, values, count as u64, last_removed)
+    }
+
+    /// Same as `from_vec()`, but clones the kept values out of a slice instead of taking ownership.
+    /// Only the last `size` values are cloned.
+    pub fn from_slice(size: usize, values: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        let count = values.len();
+        if size == 0 {
+            return Self::from(values.to_vec());
+        }
+        let discarded = count.saturating_sub(size);
+        let last_removed = discarded.checked_sub(1).map(|i| values[i].clone());
+        Self::from_newest(size, values[discarded..].to_vec(), count as u64, last_removed)
+    }
+
+    /// Same as `from_vec()`, taking the values from a VecDeque (front is the oldest value).
+    pub fn from_deque(size: usize, values: VecDeque<T>) -> Self {
+        Self::from_vec(size, Vec::from(values))
+    }
+}
+
+impl<T, A: Allocator> RollingBuffer<T, A> {
+    /// Pushes all live elements of `other` in logical order and leaves `other` empty, as `clear()` does.
+    /// Goes through `extend_from_slice()`, so each of the two runs of `other` is copied as a slice.
+    /// `other` may live in a different allocator.
+    pub fn append<B: Allocator>(&mut self, other: &mut RollingBuffer<T, B>)
+    where
+        T: Clone,
+    {
+        let (first, second) = other.as_slices();
+        self.extend_from_slice(first);
+        self.extend_from_slice(second);
+        other.clear();
+    }
+
+    /// Changes the capacity to `new_size`, keeping the newest `min(len(), new_size)` elements in logical order.
+    /// `count` and the logical indices of the kept elements don't change. When shrinking evicts elements,
+    /// the newest of them becomes `last_removed`. A `new_size` of 0 turns the buffer into an unbounded one.
+    pub fn resize(&mut self, new_size: usize) {
+        let len = self.live_len();
+        let keep = if new_size == 0 { len } else { min(len, new_size) };
+        self.make_contiguous();
+        #[cfg(feature = "zeroize")]
+        if let Some(wipe) = self.wipe_removed {
+            self.slots[..len - keep].iter_mut().for_each(wipe);
+        }
+        let evicted = self.slots.vec().drain(..len - keep).next_back();
+        if evicted.is_some() && !self.discard_removed {
+            self.last_removed = evicted;
+        }
+        self.slots.settle(new_size);
+        self.size = new_size;
+        self.mask = mask_for(new_size);
+    }
+
+    /// Pushes all values of the slice, with the same result as calling `push()` for each of them.
+    /// The values are cloned in bulk, appended to the Vec until it holds all slots and then written
+    /// into the ring with at most two slice copies, and values that would be overwritten within
+    /// the same call are skipped. For `T: Copy` every bulk copy is a plain memcpy.
+    pub fn extend_from_slice(&mut self, values: &[T])
+    where
+        T: Clone,
+    {
+        if self.size == 0 {
+            self.slots.vec().extend_from_slice(values);
+            self.count = self.count.wrapping_add(values.len() as u64);
+            return;
+        }
+        let mut values = values;
+        if !self.is_full() {
+            // the window starts at slot 0 and ends at the first unallocated slot
+            let (fill, rest) = values.split_at(min(values.len(), self.size - self.live_len()));
+            self.slots.vec().extend_from_slice(fill);
+            self.slots.settle(self.size);
+            self.count = self.count.wrapping_add(fill.len() as u64);
+            values = rest;
+        }
+        let Some((newest, rest)) = values.split_last() else {
+            return;
+        };
+        // Keep the value evicted by the newest one, so the final push leaves it in last_removed.
+        let skip = rest.len().saturating_sub(self.size);
+        let rest = &rest[skip..];
+        self.advance(skip);
+        let start = self.head;
+        let split = min(rest.len(), self.size - start);
+        #[cfg(feature = "zeroize")]
+        if let Some(wipe) = self.wipe_removed {
+            // the fill above left the ring full, so every slot written below holds a live element that these values evict
+            self.slots[start..start + split].iter_mut().for_each(wipe);
+            self.slots[..rest.len() - split].iter_mut().for_each(wipe);
+        }
+        self.slots[start..start + split].clone_from_slice(&rest[..split]);
+        self.slots[..rest.len() - split].clone_from_slice(&rest[split..]);
+        self.advance(rest.len());
+        self.push(newest.clone());
+    }
+
+    /// Accounts for `n` pushes into a full ring, without storing anything.
+    /// The caller either writes the values into their slots itself or overwrites them later on.
+    fn advance(&mut self, n: usize) {
+        debug_assert!(self.is_full());
+        self.head = (self.head + n % self.size) % self.size;
+        self.count = self.count.wrapping_add(n as u64);
+    }
+
+}
+
+impl<T, A: Allocator> RollingBuffer<T, A> {
+    /// Removes the most recently pushed element and returns it, as if that push never happened:
+    /// `count` is decremented. The element it overwrote is gone, so the window shrinks by one,
+    /// and `last_removed` is left untouched since it only tracks elements evicted by `push()`.
+    /// A wrapped buffer is made contiguous first, the popped element is then taken off the end of the Vec.
+    pub fn pop(&mut self) -> Option<T> {
+        self.make_contiguous();
+        let value = self.slots.vec().pop()?;
+        self.count = self.count.wrapping_sub(1);
+        Some(value)
+    }
+
+    /// Removes the oldest live element and returns it, so the buffer can be used as a bounded FIFO.
+    /// The logical indices of the remaining elements don't change and `len()` shrinks by one.
+    /// The remaining elements are shifted like `Vec::remove(0)` does, `RollingDeque` pops the oldest one in O(1).
+    pub fn pop_oldest(&mut self) -> Option<T> {
+        if self.live_len() == 0 {
+            return None;
+        }
+        self.make_contiguous();
+        Some(self.slots.vec().remove(0))
+    }
+
+    /// Drops the oldest elements so that at most the `n` newest stay live; `len()` becomes `min(n, len())`.
+    /// Logical indices of the kept elements don't change and the capacity is untouched.
+    pub fn keep_last(&mut self, n: usize) {
+        let excess = self.live_len().saturating_sub(n);
+        if excess > 0 {
+            self.make_contiguous();
+            self.slots.vec().drain(..excess);
+        }
+    }
+
+    /// Drops the newest elements so that at most the `n` oldest stay live, like `Vec::truncate`.
+    /// `count` goes down with every dropped element, so the next push reuses their logical indices.
+    pub fn truncate_back(&mut self, n: usize) {
+        let dropped = self.live_len().saturating_sub(n);
+        if dropped > 0 {
+            self.make_contiguous();
+            self.slots.vec().truncate(n);
+            self.count = self.count.wrapping_sub(dropped as u64);
+        }
+    }
+
+    /// Keeps only the live elements for which `f` returns true, visiting them oldest to newest.
+    /// Survivors keep their relative order and removed values are dropped. `count` is unchanged,
+    /// so the survivors are renumbered to end at the newest logical index (`count - 1`).
+    pub fn retain(&mut self, f: impl FnMut(&T) -> bool) {
+        self.make_contiguous();
+        self.slots.vec().retain(f);
+        self.slots.settle(self.size);
+    }
+
+    /// Removes consecutive repeated live elements, like `Vec::dedup`, including runs that cross the wrap point.
+    /// Logical indices are renumbered the same way as for `retain()`.
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq
+    {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    /// Removes consecutive live elements that resolve to the same key.
+    pub fn dedup_by_key<K: PartialEq>(&mut self, mut key: impl FnMut(&T) -> K) {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    /// Removes consecutive live elements for which `same(element, previous kept element)` returns true.
+    pub fn dedup_by(&mut self, mut same: impl FnMut(&T, &T) -> bool) {
+        self.make_contiguous();
+        self.slots.vec().dedup_by(|a, b| same(a, b));
+        self.slots.settle(self.size);
+    }
+}
+
+impl<T> RollingBuffer<T> {
+    /// Removes all elements from the buffer and returns them as an iterator in logical order.
+    /// The buffer is left empty (`count` is 0 and `last_removed` is `None`) but keeps its allocation,
+    /// elements that are not consumed are dropped when the iterator is dropped.
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        self.make_contiguous();
+        self.count = 0;
+        self.last_removed = None;
+        Drain::new(self.slots.vec().drain(..))
+    }
+}
+
+impl<T, A: Allocator> RollingBuffer<T, A> {
     /// Get the element at the given index, as if the buffer was a Vec
     /// 
     /// buffer of size 3, adding 1,2,3,4 and asking for the element at index 3 will return 4.
     /// Asking for index 0 will return None
-    /// since this element was overriden already.
+    /// since this element was overriden already, same as any index that hasn't been pushed yet.
     /// Example:
     /// ```
-    /// let mut buffer = RollingBuffer::<i32>::new(3, 0);
+    /// # use rolling_buffer::{buffer::buffer::RollingBuffer, prelude::*};
+    /// let mut buffer = RollingBuffer::<i32>::new(3);
     /// buffer.push(1);
     /// buffer.push(2);
     /// buffer.push(3);
@@ -68,14 +989,18 @@ where
     /// assert_eq!(buffer.get(3), Some(&4));
     /// assert_eq!(buffer.get(0), None);
     /// ```
-    fn get(&self, i: usize) -> Option<&T> {
-        if self.size > 0 {
-            Some(&self.vec[i % self.size])
-        } else if i < self.vec.len() {
-            Some(&self.vec[i])
-        } else {
-            None
+    pub fn get(&self, i: usize) -> Option<&T> {
+        let index = self.logical_to_physical(i)?;
+        Some(&self.slots[index])
+    }
+
+    /// Returns the element `n` steps back from the newest one: `get_back(0)` is the newest element,
+    /// `get_back(len() - 1)` the oldest one, anything further back returns None.
+    pub fn get_back(&self, n: usize) -> Option<&T> {
+        if n >= self.live_len() {
+            return None;
         }
+        self.get(self.live_range().end.wrapping_sub(n + 1))
     }
 
     /// Returns an option containing a reference to the first element in the rolling data.
@@ -84,27 +1009,11 @@ where
     /// Otherwise, it returns a reference to the last added element.
     /// The index calculation considers the possibility of wrapping around when
     /// the number of elements added exceeds the size of the vec.
-    fn last(&self) -> Option<&T> {
-        if self.count == 0 {
-            None
-        } else if self.size > 0 {
-            let index = (self.count as usize - 1) % self.size;
-            Some(&self.vec[index])
-        } else {
-            Some(&self.vec[self.vec.len() - 1])
-        }
-    }
-
-    /// Last added element's mutable reference.
-    fn last_mut(&mut self) -> Option<&mut T> {
-        if self.count == 0 {
+    pub fn last(&self) -> Option<&T> {
+        if self.is_empty() {
             None
-        } else if self.size > 0 {
-            let index = (self.count as usize - 1) % self.size;
-            Some(&mut self.vec[index])
         } else {
-            let index = self.vec.len() - 1;
-            Some(&mut self.vec[index])
+            Some(&self.slots[self.slot(self.live_len() - 1)])
         }
     }
 
@@ -112,6 +1021,7 @@ where
     /// 
     /// Example: 
     /// ```
+    /// # use rolling_buffer::{buffer::buffer::RollingBuffer, prelude::*};
     /// let mut buffer = RollingBuffer::<i32>::new(3);
     /// buffer.push(1);
     /// buffer.push(2);
@@ -119,71 +1029,627 @@ where
     /// buffer.push(4);
     /// assert_eq!(buffer.first(), Some(&2));
     /// ```
-    fn first(&self) -> Option<&T> {
-        if self.count == 0 {
+    pub fn first(&self) -> Option<&T> {
+        if self.is_empty() {
             None
-        } else if self.size > 0 {
-            if self.count <= self.size {
-                Some(&self.vec[0])
-            } else {
-                let index = (self.count as usize) % self.size;
-                Some(&self.vec[index])
-            }
         } else {
-            Some(&self.vec[0])
+            Some(&self.slots[self.head])
         }
     }
 
     /// Returns theoretical len as if it was a Vec.
-    fn len(&self) -> usize {
-        if self.count < self.size {
-            self.count as usize
-        } else {
-            self.vec.len()
-        }
+    pub fn len(&self) -> usize {
+        self.live_len()
     }
 
     /// Returns the maximum number of elements that can be stored.
-    fn size(&self) -> usize {
+    pub fn size(&self) -> usize {
         self.size
     }
 
-    /// Returns the underlying vector as it is stored inside the RollingBuffer.
-    fn raw(&self) -> &Vec<T> {
-        &self.vec
-    }
-
     /// Returns the last removed element. Can be very useful if needed for debugging or other purposes.
-    fn last_removed(&self) -> &Option<T> {
+    pub fn last_removed(&self) -> &Option<T> {
         &self.last_removed
     }
- 
+
     /// Returns 'expected' number of elements as if the RollingBuffer was a Vec.
     /// i.e. the number of elements that would be in the Vec if it was not a RollingBuffer.
-    fn count(&self) -> usize {
+    /// On 32-bit targets this wraps around after 2^32 pushes, see `total_pushed()` for the full count.
+    pub fn count(&self) -> usize {
         self.count as usize
     }
 
+    /// Same as `count()`, as a 64-bit number that doesn't wrap on 32-bit targets.
+    pub fn total_pushed(&self) -> u64 {
+        self.count
+    }
+
     /// Returns true if the RollingBuffer is empty.
-    fn is_empty(&self) -> bool {
-        self.count == 0
+    pub fn is_empty(&self) -> bool {
+        self.live_len() == 0
+    }
+
+    /// Returns true if every slot holds a live element, so the next push evicts the oldest one.
+    /// An unbounded buffer is never full.
+    pub fn is_full(&self) -> bool {
+        self.size > 0 && self.live_len() == self.size
     }
-    
+
+    /// Number of pushes left before the buffer is full, None for an unbounded buffer.
+    pub fn remaining_capacity(&self) -> Option<usize> {
+        (self.size > 0).then(|| self.size - self.live_len())
+    }
+
+    /// Share of the slots holding live elements, from 0.0 (empty) to 1.0 (full).
+    /// Always 0.0 for an unbounded buffer, since it never fills up.
+    pub fn fill_ratio(&self) -> f64 {
+        if self.size == 0 {
+            return 0.0;
+        }
+        self.live_len() as f64 / self.size as f64
+    }
+
     /// Creates a new Vec, which contains all elements in the RollingBuffer in correct order.
-    fn to_vec(&self) -> Vec<T> {
-        if self.size > 0 {
-            let start = if self.count <= self.size {
-                0 as usize
-            } else {
-                self.count % self.size
-            };
-            let mut vec = Vec::<T>::new();
-            for i in start..start + min(self.size, self.count) {
-                vec.push(self.vec[i % self.size].clone());
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        let mut vec = Vec::new();
+        self.to_vec_into(&mut vec);
+        vec
+    }
+
+    /// Returns an iterator over references to the elements in the same logical order as `to_vec()`.
+    /// Does not clone anything, so it is the preferred way to read the whole buffer.
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (first, second) = self.as_slices();
+        Iter::new(first, second)
+    }
+}
+
+impl<T, A: Allocator> RollingRead<T> for RollingBuffer<T, A> {
+    /// Same as the inherent `get()`.
+    fn get(&self, i: usize) -> Option<&T> {
+        RollingBuffer::get(self, i)
+    }
+
+    /// Same as the inherent `get_back()`.
+    fn get_back(&self, n: usize) -> Option<&T> {
+        RollingBuffer::get_back(self, n)
+    }
+
+    /// Same as the inherent `last()`.
+    fn last(&self) -> Option<&T> {
+        RollingBuffer::last(self)
+    }
+
+    /// Same as the inherent `first()`.
+    fn first(&self) -> Option<&T> {
+        RollingBuffer::first(self)
+    }
+
+    /// Same as the inherent `len()`.
+    fn len(&self) -> usize {
+        RollingBuffer::len(self)
+    }
+
+    /// Same as the inherent `size()`.
+    fn size(&self) -> usize {
+        RollingBuffer::size(self)
+    }
+
+    /// Same as the inherent `last_removed()`.
+    fn last_removed(&self) -> &Option<T> {
+        RollingBuffer::last_removed(self)
+    }
+
+    /// Same as the inherent `count()`.
+    fn count(&self) -> usize {
+        RollingBuffer::count(self)
+    }
+
+    /// Same as the inherent `total_pushed()`.
+    fn total_pushed(&self) -> u64 {
+        RollingBuffer::total_pushed(self)
+    }
+
+    /// Same as the inherent `is_empty()`.
+    fn is_empty(&self) -> bool {
+        RollingBuffer::is_empty(self)
+    }
+
+    /// Same as the inherent `is_full()`.
+    fn is_full(&self) -> bool {
+        RollingBuffer::is_full(self)
+    }
+
+    /// Same as the inherent `remaining_capacity()`.
+    fn remaining_capacity(&self) -> Option<usize> {
+        RollingBuffer::remaining_capacity(self)
+    }
+
+    /// Same as the inherent `fill_ratio()`.
+    fn fill_ratio(&self) -> f64 {
+        RollingBuffer::fill_ratio(self)
+    }
+
+    /// Same as the inherent `to_vec()`.
+    fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        RollingBuffer::to_vec(self)
+    }
+
+    /// Same as the inherent `iter()`.
+    fn iter(&self) -> Iter<'_, T> {
+        RollingBuffer::iter(self)
+    }
+
+    /// Same as the inherent `as_slices()`.
+    fn as_slices(&self) -> (&[T], &[T]) {
+        RollingBuffer::as_slices(self)
+    }
+}
+
+impl<T, A: Allocator> RollingBuffer<T, A> {
+    /// Adds an element to the buffer, overriding the beginning of the buffer when it is full
+    /// Here using "safe code", but it is essentially unsafe ptr::write()
+    pub fn push(&mut self, value: T) {
+        if self.is_full() {
+            let index = self.head;
+            #[cfg(feature = "zeroize")]
+            if let Some(wipe) = self.wipe_removed {
+                wipe(&mut self.slots[index]);
             }
-            vec
+            let removed = core::mem::replace(&mut self.slots[index], value);
+            if !self.discard_removed {
+                self.last_removed = Some(removed);
+            }
+            self.head = self.slot(1);
         } else {
-            self.vec.clone()
+            // a buffer that isn't full holds its live elements from slot 0 on, so nothing is evicted
+            debug_assert_eq!(self.head, 0);
+            self.slots.vec().push(value);
+            self.slots.settle(self.size);
+        }
+        self.count = self.count.wrapping_add(1);
+    }
+
+    /// Mutable version of `get()`, returns None for indices that were already evicted or not pushed yet.
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        let index = self.logical_to_physical(i)?;
+        Some(&mut self.slots[index])
+    }
+
+    /// Mutable version of `get_back()`.
+    pub fn get_back_mut(&mut self, n: usize) -> Option<&mut T> {
+        if n >= self.live_len() {
+            return None;
         }
+        self.get_mut(self.live_range().end.wrapping_sub(n + 1))
+    }
+
+    /// Last added element's mutable reference.
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        if self.is_empty() {
+            None
+        } else {
+            let index = self.slot(self.live_len() - 1);
+            Some(&mut self.slots[index])
+        }
+    }
+
+    /// Oldest live element's mutable reference, the same element `first()` returns.
+    pub fn first_mut(&mut self) -> Option<&mut T> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(&mut self.slots[self.head])
+        }
+    }
+
+    /// Empties the buffer while keeping its allocation. Stored values are dropped
+    /// instead of lingering in `raw()`, which goes back to being empty. `count` goes back to 0,
+    /// meaning logical indices restart at 0, and `last_removed` is reset to `None`.
+    pub fn clear(&mut self) {
+        self.slots.vec().clear();
+        self.count = 0;
+        self.head = 0;
+        self.last_removed = None;
+    }
+
+    /// Returns an iterator over mutable references to the live elements in logical order.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (first, second) = self.as_mut_slices();
+        IterMut::new(first, second)
+    }
+}
+
+impl<T, A: Allocator + Default> RollingWrite<T> for RollingBuffer<T, A> {
+    /// Same as `RollingBuffer::new_in()` with `A::default()`, `RollingBuffer::new()` for the global allocator.
+    fn new(size: usize) -> Self {
+        Self::new_in(size, A::default())
+    }
+
+    /// Same as the inherent `push()`.
+    fn push(&mut self, value: T) {
+        RollingBuffer::push(self, value)
+    }
+
+    /// Same as the inherent `get_mut()`.
+    fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        RollingBuffer::get_mut(self, i)
+    }
+
+    /// Same as the inherent `get_back_mut()`.
+    fn get_back_mut(&mut self, n: usize) -> Option<&mut T> {
+        RollingBuffer::get_back_mut(self, n)
+    }
+
+    /// Same as the inherent `last_mut()`.
+    fn last_mut(&mut self) -> Option<&mut T> {
+        RollingBuffer::last_mut(self)
+    }
+
+    /// Same as the inherent `first_mut()`.
+    fn first_mut(&mut self) -> Option<&mut T> {
+        RollingBuffer::first_mut(self)
+    }
+
+    /// Same as the inherent `clear()`.
+    fn clear(&mut self) {
+        RollingBuffer::clear(self)
+    }
+
+    /// Same as the inherent `iter_mut()`.
+    fn iter_mut(&mut self) -> IterMut<'_, T> {
+        RollingBuffer::iter_mut(self)
+    }
+}
+
+impl<T> IntoIterator for RollingBuffer<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    /// Consumes the buffer, yielding the owned elements in logical order without cloning.
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter::new(self.into_vec())
+    }
+}
+
+impl<'a, T, A: Allocator> IntoIterator for &'a RollingBuffer<T, A> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    /// Same as `iter()`.
+    fn into_iter(self) -> Iter<'a, T> {
+        let (first, second) = self.as_slices();
+        Iter::new(first, second)
+    }
+}
+
+impl<'a, T, A: Allocator> IntoIterator for &'a mut RollingBuffer<T, A> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    /// Same as `iter_mut()`.
+    fn into_iter(self) -> IterMut<'a, T> {
+        let (first, second) = self.as_mut_slices();
+        IterMut::new(first, second)
+    }
+}
+
+impl<T> FromIterator<T> for RollingBuffer<T> {
+    /// Collects the items into an unbounded (size 0) buffer, since `FromIterator` can't be given a size.
+    /// All items are kept in order and `count` equals the number of collected items.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(Vec::from_iter(iter))
+    }
+}
+
+impl<T> From<Vec<T>> for RollingBuffer<T> {
+    /// Wraps the Vec into an unbounded (size 0) buffer, `count` is set to the number of elements.
+    fn from(vec: Vec<T>) -> Self {
+        Self {
+            size: 0,
+            count: vec.len() as u64,
+            slots: Slots::new(vec, 0),
+            last_removed: None,
+            discard_removed: false,
+            #[cfg(feature = "zeroize")]
+            wipe_removed: None,
+            head: 0,
+            mask: None,
+            #[cfg(not(feature = "allocator_api"))]
+            alloc: Global,
+        }
+    }
+}
+
+impl<T, A: Allocator> Extend<T> for RollingBuffer<T, A> {
+    /// Pushes every item of the iterator, same as calling `push()` in a loop.
+    /// When the iterator reports (via its `size_hint()` lower bound) more items than can survive,
+    /// the ones that would be overwritten within this call are skipped without being stored.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut iter = iter.into_iter();
+        if self.size > 0 {
+            // Skipping only works once every slot is allocated, the skipped ones keep stale values until overwritten.
+            while !self.is_full() {
+                let Some(value) = iter.next() else {
+                    return;
+                };
+                self.push(value);
+            }
+            // The item right before the last `size` ones still has to be pushed to end up in last_removed.
+            let skip = iter.size_hint().0.saturating_sub(self.size + 1);
+            let skipped = iter.by_ref().take(skip).count();
+            self.advance(skipped);
+        }
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<'a, T, A: Allocator> Extend<&'a T> for RollingBuffer<T, A>
+where
+    T: Copy + 'a
+{
+    /// Copies every item of the iterator into the buffer, see `Extend<T>`.
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T> From<RollingBuffer<T>> for VecDeque<T> {
+    /// Converts the buffer into a VecDeque holding the elements in logical order, without cloning them.
+    fn from(buffer: RollingBuffer<T>) -> Self {
+        VecDeque::from(buffer.into_vec())
+    }
+}
+
+/// Two buffers are equal if they hold the same live elements in the same logical order.
+/// `size`, `count`, the physical rotation and `last_removed` are deliberately ignored, so a wrapped
+/// buffer equals a freshly seeded one with the same window, and a buffer equals an unbounded one
+/// holding the same elements. Compares the two runs in place, nothing is allocated.
+impl<T, A: Allocator> PartialEq for RollingBuffer<T, A>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.live_len() == other.live_len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T, A: Allocator> Eq for RollingBuffer<T, A> where T: Eq {}
+
+/// Shows the live elements in logical order as `data` together with the metadata that explains them,
+/// instead of the rotated Vec:
+/// `RollingBuffer { size: 4, count: 6, len: 4, head: 2, data: [3, 4, 5, 6], last_removed: Some(2) }`.
+impl<T, A: Allocator> fmt::Debug for RollingBuffer<T, A>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        /// Debug formats the live elements as a list without collecting them.
+        struct Live<'a, T, A: Allocator>(&'a RollingBuffer<T, A>);
+
+        impl<T, A: Allocator> fmt::Debug for Live<'_, T, A>
+        where
+            T: fmt::Debug,
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_list().entries(self.0.iter()).finish()
+            }
+        }
+
+        f.debug_struct("RollingBuffer")
+            .field("size", &self.size)
+            .field("count", &self.count)
+            .field("len", &self.live_len())
+            .field("head", &self.head)
+            .field("data", &Live(self))
+            .field("last_removed", &self.last_removed)
+            .finish()
+    }
+}
+
+/// Prints the live elements in logical order like a list, e.g. `[3, 4, 5]`, and `[]` when empty.
+/// Width, precision and the other flags are applied to each element, so `{:.1}` prints `[0.5, 1.0]`.
+impl<T, A: Allocator> fmt::Display for RollingBuffer<T, A>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+        for (i, value) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            fmt::Display::fmt(value, f)?;
+        }
+        f.write_str("]")
+    }
+}
+
+/// Orders buffers lexicographically by their logical sequences, the same way Vecs and slices are ordered.
+/// Like equality this ignores `size`, `count`, the physical rotation and `last_removed`.
+impl<T, A: Allocator> PartialOrd for RollingBuffer<T, A>
+where
+    T: PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T, A: Allocator> Ord for RollingBuffer<T, A>
+where
+    T: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+/// Hashes the logical sequence, the number of live elements followed by each of them in order,
+/// so that buffers that compare equal hash equally regardless of `size`, `count` or rotation.
+impl<T, A: Allocator> Hash for RollingBuffer<T, A>
+where
+    T: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.live_len());
+        self.iter().for_each(|value| value.hash(state));
+    }
+}
+
+impl<T, A: Allocator> RollingBuffer<T, A> {
+    /// Compares the live elements in logical order with a slice, run by run and without allocating.
+    fn eq_slice<U>(&self, other: &[U]) -> bool
+    where
+        T: PartialEq<U>,
+    {
+        let (first, second) = self.as_slices();
+        first.len() + second.len() == other.len() && *first == other[..first.len()] && *second == other[first.len()..]
+    }
+
+    /// Same as `eq_slice()` with the slice on the left hand side.
+    fn slice_eq<U>(slice: &[U], buffer: &Self) -> bool
+    where
+        U: PartialEq<T>,
+    {
+        let (first, second) = buffer.as_slices();
+        first.len() + second.len() == slice.len() && slice[..first.len()] == *first && slice[first.len()..] == *second
+    }
+}
+
+/// Compares the logical contents with a slice, same as comparing `to_vec()` but without allocating,
+/// so `assert_eq!(buffer, [3, 4, 5])` works directly. The same goes for the impls below.
+impl<T, U, A: Allocator> PartialEq<[U]> for RollingBuffer<T, A>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &[U]) -> bool {
+        self.eq_slice(other)
+    }
+}
+
+impl<T, U, A: Allocator> PartialEq<&[U]> for RollingBuffer<T, A>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &&[U]) -> bool {
+        self.eq_slice(other)
+    }
+}
+
+impl<T, U, A: Allocator> PartialEq<Vec<U>> for RollingBuffer<T, A>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &Vec<U>) -> bool {
+        self.eq_slice(other)
+    }
+}
+
+impl<T, U, const N: usize, A: Allocator> PartialEq<[U; N]> for RollingBuffer<T, A>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &[U; N]) -> bool {
+        self.eq_slice(other)
+    }
+}
+
+impl<T, U, A: Allocator> PartialEq<RollingBuffer<T, A>> for [U]
+where
+    U: PartialEq<T>,
+{
+    fn eq(&self, other: &RollingBuffer<T, A>) -> bool {
+        RollingBuffer::slice_eq(self, other)
+    }
+}
+
+impl<T, U, A: Allocator> PartialEq<RollingBuffer<T, A>> for &[U]
+where
+    U: PartialEq<T>,
+{
+    fn eq(&self, other: &RollingBuffer<T, A>) -> bool {
+        RollingBuffer::slice_eq(self, other)
+    }
+}
+
+impl<T, U, A: Allocator> PartialEq<RollingBuffer<T, A>> for Vec<U>
+where
+    U: PartialEq<T>,
+{
+    fn eq(&self, other: &RollingBuffer<T, A>) -> bool {
+        RollingBuffer::slice_eq(self, other)
+    }
+}
+
+impl<T, U, const N: usize, A: Allocator> PartialEq<RollingBuffer<T, A>> for [U; N]
+where
+    U: PartialEq<T>,
+{
+    fn eq(&self, other: &RollingBuffer<T, A>) -> bool {
+        RollingBuffer::slice_eq(self, other)
+    }
+}
+
+impl<T, A: Allocator> Index<usize> for RollingBuffer<T, A> {
+    type Output = T;
+
+    /// Returns the element at logical index `i`, the same one `get(i)` returns.
+    /// Panics if `i` was already evicted or hasn't been pushed yet.
+    fn index(&self, i: usize) -> &T {
+        match self.logical_to_physical(i) {
+            Some(index) => &self.slots[index],
+            None => self.out_of_window(i),
+        }
+    }
+}
+
+impl<T, A: Allocator> IndexMut<usize> for RollingBuffer<T, A> {
+    /// Mutable version of `Index`, with the same panics for evicted or not yet pushed indices.
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        match self.logical_to_physical(i) {
+            Some(index) => &mut self.slots[index],
+            None => self.out_of_window(i),
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T> RollingBuffer<T>
+where
+    T: Zeroize,
+{
+    /// From now on evicted elements are zeroized in their slot before being overwritten and dropped
+    /// instead of being kept in `last_removed`, which is zeroized right away. This covers `push()`,
+    /// `extend()`, `extend_from_slice()` and `resize()`; elements taken out with `pop()`, `drain()` and the like
+    /// are handed to the caller as usual. Store `Zeroizing<T>` elements to also cover values the buffer drops.
+    pub fn zeroize_removed(&mut self) {
+        self.discard_removed = true;
+        self.wipe_removed = Some(T::zeroize);
+        self.last_removed.zeroize();
+    }
+}
+
+/// Zeroizes every stored element, the spare capacity of the underlying Vec and `last_removed`,
+/// and leaves the buffer empty as `clear()` does. The size and the `zeroize_removed()` setting stay.
+/// `RollingBuffer` has no `Drop` impl to hook into, wrap it in `Zeroizing` to zeroize it on drop.
+#[cfg(feature = "zeroize")]
+impl<T> Zeroize for RollingBuffer<T>
+where
+    T: Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.slots.vec().zeroize();
+        self.last_removed.zeroize();
+        self.count = 0;
+        self.head = 0;
     }
 }
@@ -0,0 +1,118 @@
+use alloc::vec::Vec;
+
+use super::buffer::RollingBuffer;
+use super::error::BuildError;
+
+/// Builder for a RollingBuffer, created with `RollingBuffer::builder()`.
+///
+/// ```
+/// # use rolling_buffer::{buffer::buffer::RollingBuffer, prelude::*};
+/// let buffer = RollingBuffer::builder()
+///     .size(3)
+///     .prefill([1, 2, 3, 4])
+///     .track_removed(false)
+///     .build()
+///     .unwrap();
+/// assert_eq!(buffer.to_vec(), [2, 3, 4]);
+/// assert_eq!(*buffer.last_removed(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingBufferBuilder<T> {
+    size: usize,
+    prefill: Vec<T>,
+    track_removed: bool,
+    strict: bool,
+    max_size: Option<usize>,
+}
+
+impl<T> Default for RollingBufferBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> RollingBufferBuilder<T> {
+    /// Creates a builder for an empty unbounded buffer that tracks removed elements.
+    pub fn new() -> Self {
+        Self {
+            size: 0,
+            prefill: Vec::new(),
+            track_removed: true,
+            strict: false,
+            max_size: None,
+        }
+    }
+
+    /// Sets the maximum number of elements, 0 (the default) makes the buffer unbounded.
+    pub fn size(mut self, size: usize) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Makes the buffer unbounded, same as `size(0)`.
+    pub fn unbounded(self) -> Self {
+        self.size(0)
+    }
+
+    /// Values pushed into the buffer on construction, in order.
+    pub fn prefill<I: IntoIterator<Item = T>>(mut self, values: I) -> Self {
+        self.prefill = values.into_iter().collect();
+        self
+    }
+
+    /// Whether evicted elements are kept in `last_removed` (the default) or dropped right away.
+    pub fn track_removed(mut self, track_removed: bool) -> Self {
+        self.track_removed = track_removed;
+        self
+    }
+
+    /// In strict mode a prefill longer than the size is an error instead of evicting the oldest values.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Upper limit for the size, a larger one is rejected with `BuildError::SizeTooLarge`.
+    /// Useful when the size comes from user input.
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    fn validate(&self) -> Result<(), BuildError> {
+        if let Some(max) = self.max_size.filter(|&max| self.size > max) {
+            return Err(BuildError::SizeTooLarge { size: self.size, max });
+        }
+        if self.strict && self.size > 0 && self.prefill.len() > self.size {
+            return Err(BuildError::PrefillTooLong {
+                size: self.size,
+                len: self.prefill.len(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Builds the buffer. Slots that the prefill doesn't cover are allocated on push.
+    pub fn build(self) -> Result<RollingBuffer<T>, BuildError> {
+        self.assemble()
+    }
+
+    /// Builds a buffer that is full from the start.
+    /// The prefill has to cover every slot, otherwise `BuildError::PrefillTooShort` is returned.
+    pub fn build_full(self) -> Result<RollingBuffer<T>, BuildError> {
+        if self.prefill.len() < self.size {
+            return Err(BuildError::PrefillTooShort {
+                size: self.size,
+                len: self.prefill.len(),
+            });
+        }
+        self.assemble()
+    }
+
+    fn assemble(self) -> Result<RollingBuffer<T>, BuildError> {
+        self.validate()?;
+        let mut buffer = RollingBuffer::from_vec(self.size, self.prefill);
+        buffer.set_track_removed(self.track_removed);
+        Ok(buffer)
+    }
+}
@@ -0,0 +1,38 @@
+use defmt::{Format, Formatter, write};
+
+use super::buffer::RollingBuffer;
+
+/// Logs the size, the number of pushes and the live elements in logical order,
+/// e.g. `RollingBuffer { size: 4, count: 6, data: [3, 4, 5, 6] }`. The physical layout and
+/// `last_removed` are left out to keep the frames small, `Debug` prints them on the host.
+///
+/// ```ignore
+/// use rolling_buffer::{buffer::buffer::RollingBuffer, prelude::*};
+///
+/// #[derive(defmt::Format)]
+/// struct Telemetry {
+///     channel: u8,
+///     samples: RollingBuffer<u16>,
+/// }
+///
+/// let mut samples = RollingBuffer::new(4);
+/// samples.extend([512, 530, 498, 505, 517]);
+/// defmt::info!("{}", samples);
+/// // INFO RollingBuffer { size: 4, count: 5, data: [530, 498, 505, 517] }
+/// defmt::info!("{}", Telemetry { channel: 2, samples });
+/// ```
+impl<T> Format for RollingBuffer<T>
+where
+    T: Format,
+{
+    fn format(&self, f: Formatter<'_>) {
+        write!(f, "RollingBuffer {{ size: {=usize}, count: {=u64}, data: [", self.size(), self.total_pushed());
+        for (i, value) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ");
+            }
+            write!(f, "{}", value);
+        }
+        write!(f, "] }}");
+    }
+}
@@ -0,0 +1,202 @@
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use super::iter::{Iter, IterMut};
+use super::traits::{RollingRead, RollingWrite};
+
+/// RollingDeque is a rolling buffer backed by a VecDeque, with the same observable behaviour as RollingBuffer:
+/// the same logical indices, `count`, `last_removed` and unbounded mode for a size of 0.
+/// Popping the oldest element doesn't shift the others, unlike `RollingBuffer::pop_oldest()`.
+///
+/// ['size']: size is the maximum number of elements that the buffer can hold, 0 for an unbounded buffer
+/// ['deque']: deque holds the live elements, oldest first
+/// ['last_removed']: last_removed is the last element that was evicted by a push
+/// ['count']: count is the number of elements in the buffer as if the buffer was Vec, logical indices are count truncated to usize
+#[derive(Debug, Clone, Default)]
+pub struct RollingDeque<T> {
+    size: usize,
+    deque: VecDeque<T>,
+    last_removed: Option<T>,
+    count: u64,
+}
+
+impl<T> RollingDeque<T> {
+    /// Returns the live elements, oldest first.
+    pub fn as_deque(&self) -> &VecDeque<T> {
+        &self.deque
+    }
+
+    /// Removes the oldest live element and returns it, same as `RollingBuffer::pop_oldest()`:
+    /// the logical indices of the remaining elements don't change and `last_removed` is left untouched.
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.deque.pop_front()
+    }
+
+    /// Removes the most recently pushed element and returns it, same as `RollingBuffer::pop()`:
+    /// `count` is decremented and `last_removed` is left untouched.
+    pub fn pop_back(&mut self) -> Option<T> {
+        let value = self.deque.pop_back()?;
+        self.count = self.count.wrapping_sub(1);
+        Some(value)
+    }
+
+    /// Maps a logical index to a position in the deque, None if it was evicted or not pushed yet.
+    fn position(&self, i: usize) -> Option<usize> {
+        let start = (self.count as usize).wrapping_sub(self.deque.len());
+        let k = i.wrapping_sub(start);
+        (k < self.deque.len()).then_some(k)
+    }
+}
+
+impl<T> RollingRead<T> for RollingDeque<T> {
+    /// Get the element at the given logical index, None for indices that were already evicted or not pushed yet.
+    fn get(&self, i: usize) -> Option<&T> {
+        self.deque.get(self.position(i)?)
+    }
+
+    /// Returns the element `n` steps back from the newest one, `get_back(0)` is the newest element.
+    fn get_back(&self, n: usize) -> Option<&T> {
+        if n >= self.deque.len() {
+            return None;
+        }
+        let index = self.deque.len() - 1 - n;
+        self.deque.get(index)
+    }
+
+    /// Returns the most recently pushed live element.
+    fn last(&self) -> Option<&T> {
+        self.deque.back()
+    }
+
+    /// Returns the oldest live element.
+    fn first(&self) -> Option<&T> {
+        self.deque.front()
+    }
+
+    /// Returns the number of live elements.
+    fn len(&self) -> usize {
+        self.deque.len()
+    }
+
+    /// Returns the maximum number of elements that can be stored, 0 for an unbounded buffer.
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the last element evicted by a push.
+    fn last_removed(&self) -> &Option<T> {
+        &self.last_removed
+    }
+
+    /// Returns the number of pushed elements as if the buffer was a Vec, truncated to usize.
+    fn count(&self) -> usize {
+        self.count as usize
+    }
+
+    /// Same as `count()`, as a 64-bit number that doesn't wrap on 32-bit targets.
+    fn total_pushed(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns true if there are no live elements.
+    fn is_empty(&self) -> bool {
+        self.deque.is_empty()
+    }
+
+    /// Returns true if the next push evicts the oldest element. An unbounded buffer is never full.
+    fn is_full(&self) -> bool {
+        self.size > 0 && self.deque.len() == self.size
+    }
+
+    /// Number of pushes left before the buffer is full, None for an unbounded buffer.
+    fn remaining_capacity(&self) -> Option<usize> {
+        (self.size > 0).then(|| self.size - self.deque.len())
+    }
+
+    /// Share of the slots holding live elements, always 0.0 for an unbounded buffer.
+    fn fill_ratio(&self) -> f64 {
+        if self.size == 0 {
+            return 0.0;
+        }
+        self.deque.len() as f64 / self.size as f64
+    }
+
+    /// Creates a new Vec with the live elements, oldest first.
+    fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.deque.iter().cloned().collect()
+    }
+
+    /// Returns an iterator over references to the live elements, oldest first.
+    fn iter(&self) -> Iter<'_, T> {
+        let (first, second) = self.deque.as_slices();
+        Iter::new(first, second)
+    }
+
+    /// Returns the live elements as the two runs of the VecDeque, oldest first.
+    fn as_slices(&self) -> (&[T], &[T]) {
+        self.deque.as_slices()
+    }
+}
+
+impl<T> RollingWrite<T> for RollingDeque<T> {
+    /// Creates a new RollingDeque with the given size, reserving room for `size` elements up front.
+    /// If the size is 0, the buffer will behave as a normal Vec.
+    fn new(size: usize) -> Self {
+        Self {
+            size,
+            deque: VecDeque::with_capacity(size),
+            last_removed: None,
+            count: 0,
+        }
+    }
+
+    /// Adds an element to the buffer, evicting the oldest one into `last_removed` when it is full.
+    fn push(&mut self, value: T) {
+        if self.size > 0 && self.deque.len() == self.size {
+            self.last_removed = self.deque.pop_front();
+        }
+        self.deque.push_back(value);
+        self.count = self.count.wrapping_add(1);
+    }
+
+    /// Mutable version of `get()`.
+    fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        let index = self.position(i)?;
+        self.deque.get_mut(index)
+    }
+
+    /// Mutable version of `get_back()`.
+    fn get_back_mut(&mut self, n: usize) -> Option<&mut T> {
+        if n >= self.deque.len() {
+            return None;
+        }
+        let index = self.deque.len() - 1 - n;
+        self.deque.get_mut(index)
+    }
+
+    /// Most recently pushed element's mutable reference.
+    fn last_mut(&mut self) -> Option<&mut T> {
+        self.deque.back_mut()
+    }
+
+    /// Oldest live element's mutable reference.
+    fn first_mut(&mut self) -> Option<&mut T> {
+        self.deque.front_mut()
+    }
+
+    /// Empties the buffer while keeping its allocation, `count` goes back to 0 and `last_removed` to `None`.
+    fn clear(&mut self) {
+        self.deque.clear();
+        self.count = 0;
+        self.last_removed = None;
+    }
+
+    /// Returns an iterator over mutable references to the live elements, oldest first.
+    fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (first, second) = self.deque.as_mut_slices();
+        IterMut::new(first, second)
+    }
+}
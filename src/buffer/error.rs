@@ -0,0 +1,79 @@
+use core::error::Error;
+use core::fmt;
+
+/// Error returned by `RollingBufferBuilder` when the requested configuration is invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildError {
+    /// Strict mode is on and the prefill holds more values than the buffer can keep.
+    PrefillTooLong { size: usize, len: usize },
+    /// The buffer was built with `build_full()`, but the prefill doesn't cover every slot.
+    PrefillTooShort { size: usize, len: usize },
+    /// The size exceeds the limit set with `max_size()`.
+    SizeTooLarge { size: usize, max: usize },
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::PrefillTooLong { size, len } => {
+                write!(f, "prefill of {len} values does not fit into a buffer of size {size}")
+            }
+            BuildError::PrefillTooShort { size, len } => {
+                write!(f, "prefill of {len} values does not cover a buffer of size {size}")
+            }
+            BuildError::SizeTooLarge { size, max } => {
+                write!(f, "buffer size {size} exceeds the maximum of {max}")
+            }
+        }
+    }
+}
+
+impl Error for BuildError {}
+
+/// Error returned by `RollingBuffer::from_raw_parts()` when the parts violate the buffer invariants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RawPartsError {
+    /// A fixed size buffer can't hold more than `size` slots in its Vec, a shorter Vec is taken as the live elements.
+    LengthMismatch { size: usize, len: usize },
+    /// Every stored element is live and was pushed at some point, so `count` can't be below the Vec length.
+    CountMismatch { count: usize, len: usize },
+}
+
+impl fmt::Display for RawPartsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RawPartsError::LengthMismatch { size, len } => {
+                write!(f, "vec of length {len} exceeds buffer size {size}")
+            }
+            RawPartsError::CountMismatch { count, len } => {
+                write!(f, "count {count} is below length {len} of an unbounded buffer")
+            }
+        }
+    }
+}
+
+impl Error for RawPartsError {}
+
+/// Error returned by `RollingBuffer::get_checked()` for a logical index outside of the live window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GetError {
+    /// The element was already overwritten, `oldest_live` is the oldest index still stored.
+    Evicted { index: usize, oldest_live: usize },
+    /// The element hasn't been pushed yet, `next` is the index the next push will get.
+    NotYetPushed { index: usize, next: usize },
+}
+
+impl fmt::Display for GetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GetError::Evicted { index, oldest_live } => {
+                write!(f, "logical index {index} evicted; oldest live index is {oldest_live}")
+            }
+            GetError::NotYetPushed { index, next } => {
+                write!(f, "logical index {index} not yet pushed; next index is {next}")
+            }
+        }
+    }
+}
+
+impl Error for GetError {}
@@ -0,0 +1,25 @@
+use super::buffer::RollingBuffer;
+
+/// Extension trait for collecting any iterator into a RollingBuffer of a given size.
+pub trait RollingIteratorExt: Iterator {
+    /// Collects the iterator into a RollingBuffer of the given size, as if every item was pushed in order:
+    /// only the last `size` items are kept, `count` equals the number of consumed items
+    /// and `last_removed` is the last discarded item.
+    ///
+    /// ```
+    /// # use rolling_buffer::{buffer::ext::RollingIteratorExt, prelude::*};
+    /// let buffer = (1..=10).collect_rolling(3);
+    /// assert_eq!(buffer.to_vec(), [8, 9, 10]);
+    /// assert_eq!(buffer.count(), 10);
+    /// ```
+    fn collect_rolling(self, size: usize) -> RollingBuffer<Self::Item>
+    where
+        Self: Sized,
+    {
+        let mut buffer = RollingBuffer::new(size);
+        buffer.extend(self);
+        buffer
+    }
+}
+
+impl<I> RollingIteratorExt for I where I: Iterator {}
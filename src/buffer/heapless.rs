@@ -0,0 +1,31 @@
+use super::storage::{RollingStorage, Storage};
+
+/// RollingHeapless is a fixed size rolling buffer stored in a `heapless::Vec<T, N>`, so it lives wherever
+/// the buffer itself is placed and never touches the heap. Like RollingBuffer the Vec grows on push
+/// until it holds all `N` slots, and the logical indices, `count` and `last_removed` are the same as
+/// for a RollingBuffer of size `N`. `N` must not be 0 since there is no unbounded mode.
+pub type RollingHeapless<T, const N: usize> = RollingStorage<T, ::heapless::Vec<T, N>>;
+
+impl<T, const N: usize> Storage<T> for ::heapless::Vec<T, N> {
+    const SIZE: usize = N;
+    const NAME: &'static str = "RollingHeapless";
+
+    /// No slot is initialized until it is pushed.
+    fn empty() -> Self {
+        const { assert!(N > 0, "a RollingHeapless needs at least one slot") };
+        ::heapless::Vec::new()
+    }
+
+    /// Pushes the value, the slots below `index` are the only initialized ones.
+    fn store(&mut self, index: usize, value: T) {
+        debug_assert_eq!(index, self.len());
+        if self.push(value).is_err() {
+            unreachable!("fewer than {N} slots are allocated");
+        }
+    }
+
+    /// Drops the stored values.
+    fn reset(&mut self) {
+        self.truncate(0);
+    }
+}
@@ -0,0 +1,612 @@
+use alloc::vec::{self, Vec};
+use core::iter::FusedIterator;
+use core::ops::Sub;
+use core::slice;
+
+/// Narrows the two runs holding the logical view down to the logical positions `start..end`.
+pub(crate) fn split_range<'a, T>(first: &'a [T], second: &'a [T], start: usize, end: usize) -> (&'a [T], &'a [T]) {
+    let split = first.len();
+    if end <= split {
+        (&first[start..end], &[])
+    } else if start >= split {
+        (&second[start - split..end - split], &[])
+    } else {
+        (&first[start..], &second[..end - split])
+    }
+}
+
+/// Borrowing iterator over the elements of a RollingBuffer in logical order (oldest to newest).
+///
+/// The live elements of a wrapped buffer occupy at most two contiguous runs of the
+/// underlying Vec, so the iterator simply walks the first run and then the second one.
+#[derive(Debug)]
+pub struct Iter<'a, T> {
+    first: slice::Iter<'a, T>,
+    second: slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    pub(crate) fn new(first: &'a [T], second: &'a [T]) -> Self {
+        Self {
+            first: first.iter(),
+            second: second.iter(),
+        }
+    }
+
+    /// Iterator over the logical positions `start..end` of the two runs.
+    pub(crate) fn range(first: &'a [T], second: &'a [T], start: usize, end: usize) -> Self {
+        let (first, second) = split_range(first, second, start, end);
+        Self::new(first, second)
+    }
+
+    /// Narrows the remaining elements down to the newest `n`, or all of them if there are fewer.
+    pub(crate) fn newest(self, n: usize) -> Self {
+        let (first, second) = (self.first.as_slice(), self.second.as_slice());
+        let len = first.len() + second.len();
+        Self::range(first, second, len - n.min(len), len)
+    }
+}
+
+/// Cloning only copies the two slice iterators, so unlike a derive this doesn't need `T: Clone`.
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            first: self.first.clone(),
+            second: self.second.clone(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        match self.first.next() {
+            Some(value) => Some(value),
+            None => self.second.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.first.len() + self.second.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        match self.second.next_back() {
+            Some(value) => Some(value),
+            None => self.first.next_back(),
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// Mutable iterator over the elements of a RollingBuffer in logical order (oldest to newest).
+///
+/// Only the live elements are visited, the padding of an underfilled buffer is skipped.
+#[derive(Debug)]
+pub struct IterMut<'a, T> {
+    first: slice::IterMut<'a, T>,
+    second: slice::IterMut<'a, T>,
+}
+
+impl<'a, T> IterMut<'a, T> {
+    pub(crate) fn new(first: &'a mut [T], second: &'a mut [T]) -> Self {
+        Self {
+            first: first.iter_mut(),
+            second: second.iter_mut(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        match self.first.next() {
+            Some(value) => Some(value),
+            None => self.second.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.first.len() + self.second.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        match self.second.next_back() {
+            Some(value) => Some(value),
+            None => self.first.next_back(),
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// Owning iterator over the elements of a RollingBuffer in logical order (oldest to newest).
+///
+/// Takes over the allocation of the buffer, elements that are never yielded are dropped with it.
+#[derive(Debug, Clone)]
+pub struct IntoIter<T> {
+    inner: vec::IntoIter<T>,
+}
+
+impl<T> IntoIter<T> {
+    pub(crate) fn new(vec: Vec<T>) -> Self {
+        Self {
+            inner: vec.into_iter(),
+        }
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+/// Iterator over the elements of a RollingBuffer paired with their logical indices,
+/// i.e. the indices accepted by `get()`.
+#[derive(Debug, Clone)]
+pub struct IterIndexed<'a, T> {
+    index: usize,
+    inner: Iter<'a, T>,
+}
+
+impl<'a, T> IterIndexed<'a, T> {
+    pub(crate) fn new(index: usize, inner: Iter<'a, T>) -> Self {
+        Self { index, inner }
+    }
+}
+
+impl<'a, T> Iterator for IterIndexed<'a, T> {
+    type Item = (usize, &'a T);
+
+    fn next(&mut self) -> Option<(usize, &'a T)> {
+        let value = self.inner.next()?;
+        let index = self.index;
+        self.index = self.index.wrapping_add(1);
+        Some((index, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterIndexed<'a, T> {
+    fn next_back(&mut self) -> Option<(usize, &'a T)> {
+        let value = self.inner.next_back()?;
+        Some((self.index.wrapping_add(self.inner.len()), value))
+    }
+}
+
+impl<T> ExactSizeIterator for IterIndexed<'_, T> {}
+
+impl<T> FusedIterator for IterIndexed<'_, T> {}
+
+/// Iterator over the elements pushed since a given logical index, see `RollingBuffer::iter_from()`.
+///
+/// Remembers where it actually started and how many of the requested elements were already evicted.
+#[derive(Debug, Clone)]
+pub struct IterFrom<'a, T> {
+    inner: Iter<'a, T>,
+    start: usize,
+    skipped: usize,
+}
+
+impl<'a, T> IterFrom<'a, T> {
+    pub(crate) fn new(inner: Iter<'a, T>, start: usize, skipped: usize) -> Self {
+        Self { inner, start, skipped }
+    }
+
+    /// Logical index of the first element yielded by the iterator.
+    pub fn start_index(&self) -> usize {
+        self.start
+    }
+
+    /// Number of requested elements that had already been evicted from the buffer.
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+}
+
+impl<'a, T> Iterator for IterFrom<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterFrom<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IterFrom<'_, T> {}
+
+impl<T> FusedIterator for IterFrom<'_, T> {}
+
+/// Iterator over a range of logical indices clipped to the live window, see `RollingBuffer::range()`.
+#[derive(Debug, Clone)]
+pub struct RangeIter<'a, T> {
+    inner: Iter<'a, T>,
+    start: usize,
+}
+
+impl<'a, T> RangeIter<'a, T> {
+    pub(crate) fn new(inner: Iter<'a, T>, start: usize) -> Self {
+        Self { inner, start }
+    }
+
+    /// Logical index of the first element of the clipped range.
+    pub fn start_index(&self) -> usize {
+        self.start
+    }
+}
+
+impl<'a, T> Iterator for RangeIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for RangeIter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for RangeIter<'_, T> {}
+
+impl<T> FusedIterator for RangeIter<'_, T> {}
+
+/// Iterator over overlapping windows of `k` consecutive elements in logical order.
+///
+/// A window that crosses the physical wrap point can't be a single `&[T]`, so every window
+/// is yielded as an [`Iter`] over its `k` elements, which keeps the iteration allocation-free.
+#[derive(Debug, Clone)]
+pub struct Windows<'a, T> {
+    first: &'a [T],
+    second: &'a [T],
+    k: usize,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T> Windows<'a, T> {
+    pub(crate) fn new(first: &'a [T], second: &'a [T], k: usize) -> Self {
+        assert!(k != 0, "window size must be non-zero");
+        let len = first.len() + second.len();
+        Self {
+            first,
+            second,
+            k,
+            front: 0,
+            back: (len + 1).saturating_sub(k),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Windows<'a, T> {
+    type Item = Iter<'a, T>;
+
+    fn next(&mut self) -> Option<Iter<'a, T>> {
+        if self.front >= self.back {
+            return None;
+        }
+        let start = self.front;
+        self.front += 1;
+        Some(Iter::range(self.first, self.second, start, start + self.k))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back.saturating_sub(self.front);
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Windows<'a, T> {
+    fn next_back(&mut self) -> Option<Iter<'a, T>> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(Iter::range(self.first, self.second, self.back, self.back + self.k))
+    }
+}
+
+impl<T> ExactSizeIterator for Windows<'_, T> {}
+
+impl<T> FusedIterator for Windows<'_, T> {}
+
+/// Iterator over non-overlapping chunks of `k` elements in logical order, the last chunk may be shorter.
+///
+/// Like [`Windows`], every chunk is yielded as an [`Iter`] because it may cross the wrap point.
+#[derive(Debug, Clone)]
+pub struct Chunks<'a, T> {
+    first: &'a [T],
+    second: &'a [T],
+    k: usize,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T> Chunks<'a, T> {
+    pub(crate) fn new(first: &'a [T], second: &'a [T], k: usize) -> Self {
+        assert!(k != 0, "chunk size must be non-zero");
+        Self {
+            first,
+            second,
+            k,
+            front: 0,
+            back: first.len() + second.len(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Chunks<'a, T> {
+    type Item = Iter<'a, T>;
+
+    fn next(&mut self) -> Option<Iter<'a, T>> {
+        if self.front >= self.back {
+            return None;
+        }
+        let start = self.front;
+        self.front = (start + self.k).min(self.back);
+        Some(Iter::range(self.first, self.second, start, self.front))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.back - self.front).div_ceil(self.k);
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Chunks<'a, T> {
+    fn next_back(&mut self) -> Option<Iter<'a, T>> {
+        if self.front >= self.back {
+            return None;
+        }
+        let end = self.back;
+        let remainder = (end - self.front) % self.k;
+        self.back = end - if remainder == 0 { self.k } else { remainder };
+        Some(Iter::range(self.first, self.second, self.back, end))
+    }
+}
+
+impl<T> ExactSizeIterator for Chunks<'_, T> {}
+
+impl<T> FusedIterator for Chunks<'_, T> {}
+
+/// Iterator over non-overlapping chunks of exactly `k` elements in logical order.
+///
+/// The trailing elements that don't fill a whole chunk are left out and available through `remainder()`.
+#[derive(Debug, Clone)]
+pub struct ChunksExact<'a, T> {
+    first: &'a [T],
+    second: &'a [T],
+    k: usize,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T> ChunksExact<'a, T> {
+    pub(crate) fn new(first: &'a [T], second: &'a [T], k: usize) -> Self {
+        assert!(k != 0, "chunk size must be non-zero");
+        let len = first.len() + second.len();
+        Self {
+            first,
+            second,
+            k,
+            front: 0,
+            back: len - len % k,
+        }
+    }
+
+    /// Returns the elements at the end of the logical view that don't fill a whole chunk.
+    pub fn remainder(&self) -> Iter<'a, T> {
+        let len = self.first.len() + self.second.len();
+        Iter::range(self.first, self.second, len - len % self.k, len)
+    }
+}
+
+impl<'a, T> Iterator for ChunksExact<'a, T> {
+    type Item = Iter<'a, T>;
+
+    fn next(&mut self) -> Option<Iter<'a, T>> {
+        if self.front >= self.back {
+            return None;
+        }
+        let start = self.front;
+        self.front += self.k;
+        Some(Iter::range(self.first, self.second, start, self.front))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.back - self.front) / self.k;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for ChunksExact<'a, T> {
+    fn next_back(&mut self) -> Option<Iter<'a, T>> {
+        if self.front >= self.back {
+            return None;
+        }
+        let end = self.back;
+        self.back -= self.k;
+        Some(Iter::range(self.first, self.second, self.back, end))
+    }
+}
+
+impl<T> ExactSizeIterator for ChunksExact<'_, T> {}
+
+impl<T> FusedIterator for ChunksExact<'_, T> {}
+
+/// Iterator applying a function to every pair of consecutive elements in logical order.
+/// Yields `len - 1` items, or nothing when fewer than two elements are stored.
+#[derive(Debug, Clone)]
+pub struct MapPairs<'a, T, F> {
+    previous: Option<&'a T>,
+    inner: Iter<'a, T>,
+    f: F,
+}
+
+/// Iterator over the differences between consecutive elements, see `RollingBuffer::deltas()`.
+pub type Deltas<'a, T> = MapPairs<'a, T, fn(&T, &T) -> T>;
+
+impl<'a, T, F> MapPairs<'a, T, F> {
+    pub(crate) fn new(mut inner: Iter<'a, T>, f: F) -> Self {
+        Self {
+            previous: inner.next(),
+            inner,
+            f,
+        }
+    }
+}
+
+impl<'a, T, F, B> Iterator for MapPairs<'a, T, F>
+where
+    F: FnMut(&'a T, &'a T) -> B,
+{
+    type Item = B;
+
+    fn next(&mut self) -> Option<B> {
+        let previous = self.previous?;
+        let next = self.inner.next()?;
+        self.previous = Some(next);
+        Some((self.f)(previous, next))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T, F, B> ExactSizeIterator for MapPairs<'a, T, F> where F: FnMut(&'a T, &'a T) -> B {}
+
+impl<'a, T, F, B> FusedIterator for MapPairs<'a, T, F> where F: FnMut(&'a T, &'a T) -> B {}
+
+/// Iterator applying a function to the elements of two buffers paired up from the newest one,
+/// see `RollingBuffer::zip_map()`. Yields `min(len)` items, oldest pair first.
+#[derive(Debug, Clone)]
+pub struct ZipMap<'a, T, U, F> {
+    left: Iter<'a, T>,
+    right: Iter<'a, U>,
+    f: F,
+}
+
+impl<'a, T, U, F> ZipMap<'a, T, U, F> {
+    pub(crate) fn new(left: Iter<'a, T>, right: Iter<'a, U>, f: F) -> Self {
+        let len = left.len().min(right.len());
+        Self {
+            left: left.newest(len),
+            right: right.newest(len),
+            f,
+        }
+    }
+}
+
+impl<'a, T, U, F, B> Iterator for ZipMap<'a, T, U, F>
+where
+    F: FnMut(&'a T, &'a U) -> B,
+{
+    type Item = B;
+
+    fn next(&mut self) -> Option<B> {
+        let left = self.left.next()?;
+        let right = self.right.next()?;
+        Some((self.f)(left, right))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.left.size_hint()
+    }
+}
+
+impl<'a, T, U, F, B> ExactSizeIterator for ZipMap<'a, T, U, F> where F: FnMut(&'a T, &'a U) -> B {}
+
+impl<'a, T, U, F, B> FusedIterator for ZipMap<'a, T, U, F> where F: FnMut(&'a T, &'a U) -> B {}
+
+pub(crate) fn delta<T>(previous: &T, next: &T) -> T
+where
+    T: Sub<Output = T> + Clone,
+{
+    next.clone() - previous.clone()
+}
+
+/// Draining iterator over the elements of a RollingBuffer in logical order, see `RollingBuffer::drain()`.
+///
+/// The buffer is already logically empty when the iterator is created, elements that are
+/// not consumed are dropped together with the iterator.
+#[derive(Debug)]
+pub struct Drain<'a, T> {
+    inner: vec::Drain<'a, T>,
+}
+
+impl<'a, T> Drain<'a, T> {
+    pub(crate) fn new(inner: vec::Drain<'a, T>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Drain<'_, T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<'_, T> {}
+
+impl<T> FusedIterator for Drain<'_, T> {}
@@ -0,0 +1,86 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{Deserialize, DeserializeSeed, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use super::buffer::RollingBuffer;
+
+/// Compact serde representation of a RollingBuffer: the live elements as a plain sequence, oldest first,
+/// e.g. `[3, 4, 5, 6]`, for tools that expect a bare array. Serializes without collecting the elements.
+/// The size is not part of it, so deserializing goes through a `LogicalSeed` or `deserialize()`.
+pub struct LogicalSerde<'a, T>(pub &'a RollingBuffer<T>);
+
+impl<T> Serialize for LogicalSerde<'_, T>
+where
+    T: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter())
+    }
+}
+
+/// Deserializes a sequence into a RollingBuffer of the given size, as if every element was pushed in order:
+/// only the last `size` elements are kept, `count` is the length of the sequence
+/// and `last_removed` the last dropped element.
+pub struct LogicalSeed<T> {
+    size: usize,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> LogicalSeed<T> {
+    /// Creates a seed for a buffer of the given size, 0 for an unbounded one.
+    pub fn new(size: usize) -> Self {
+        Self { size, marker: PhantomData }
+    }
+}
+
+impl<'de, T> DeserializeSeed<'de> for LogicalSeed<T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = RollingBuffer<T>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, T> Visitor<'de> for LogicalSeed<T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = RollingBuffer<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a sequence of buffer elements")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut buffer = RollingBuffer::new(self.size);
+        while let Some(value) = seq.next_element()? {
+            buffer.push(value);
+        }
+        Ok(buffer)
+    }
+}
+
+/// Same as serializing `LogicalSerde(buffer)`, for `#[serde(serialize_with = ...)]`.
+pub fn serialize<T, S>(buffer: &RollingBuffer<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: Serializer,
+{
+    LogicalSerde(buffer).serialize(serializer)
+}
+
+/// Same as deserializing with `LogicalSeed::new(SIZE)`, for `#[serde(deserialize_with = ...)]`.
+/// Together with `serialize()` a field is stored as a bare array with:
+/// `#[serde(serialize_with = "logical_serde::serialize", deserialize_with = "logical_serde::deserialize::<4, _, _>")]`
+pub fn deserialize<'de, const SIZE: usize, T, D>(deserializer: D) -> Result<RollingBuffer<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    LogicalSeed::new(SIZE).deserialize(deserializer)
+}
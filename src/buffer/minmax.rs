@@ -0,0 +1,113 @@
+use alloc::collections::VecDeque;
+use core::ops::Deref;
+
+use super::buffer::RollingBuffer;
+
+/// RollingMinMax owns a RollingBuffer and keeps track of the minimum and maximum of its live window
+/// in amortized O(1) per push, using a monotonic deque for each of them. Elements are only added
+/// through `push()`, so the deques never get out of sync with the window; the buffer itself
+/// is available read-only through `Deref`.
+///
+/// Like `RollingStats::min_indexed()` and `max_indexed()`, equal extremes resolve to the oldest one and elements
+/// that don't compare with themselves (NaN) are never reported.
+///
+/// ['buffer']: buffer is the tracked window
+/// ['mins']: mins holds the logical indices of the candidates for the minimum, their values non-decreasing from front to back
+/// ['maxs']: maxs holds the logical indices of the candidates for the maximum, their values non-increasing from front to back
+#[derive(Debug, Clone)]
+pub struct RollingMinMax<T> {
+    buffer: RollingBuffer<T>,
+    mins: VecDeque<u64>,
+    maxs: VecDeque<u64>,
+}
+
+impl<T> RollingMinMax<T>
+where
+    T: PartialOrd,
+{
+    /// Creates an empty tracker over a new RollingBuffer of the given size, 0 for an unbounded one.
+    pub fn new(size: usize) -> Self {
+        Self::from_buffer(RollingBuffer::new(size))
+    }
+
+    /// Tracks an existing buffer, scanning its live elements once.
+    pub fn from_buffer(buffer: RollingBuffer<T>) -> Self {
+        let mut tracker = Self {
+            buffer,
+            mins: VecDeque::new(),
+            maxs: VecDeque::new(),
+        };
+        let start = tracker.buffer.total_pushed() - tracker.buffer.len() as u64;
+        for index in start..tracker.buffer.total_pushed() {
+            admit(&tracker.buffer, &mut tracker.mins, &mut tracker.maxs, index);
+        }
+        tracker
+    }
+
+    /// Pushes into the buffer and updates the minimum and maximum.
+    pub fn push(&mut self, value: T) {
+        self.buffer.push(value);
+        let oldest = self.buffer.total_pushed() - self.buffer.len() as u64;
+        while self.mins.front().is_some_and(|&index| index < oldest) {
+            self.mins.pop_front();
+        }
+        while self.maxs.front().is_some_and(|&index| index < oldest) {
+            self.maxs.pop_front();
+        }
+        admit(&self.buffer, &mut self.mins, &mut self.maxs, self.buffer.total_pushed() - 1);
+    }
+
+    /// Current smallest live element, None if the window holds no comparable elements.
+    pub fn current_min(&self) -> Option<&T> {
+        self.mins.front().map(|&index| value(&self.buffer, index))
+    }
+
+    /// Current largest live element, None if the window holds no comparable elements.
+    pub fn current_max(&self) -> Option<&T> {
+        self.maxs.front().map(|&index| value(&self.buffer, index))
+    }
+
+    /// Empties the buffer as `RollingWrite::clear()` does.
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.mins.clear();
+        self.maxs.clear();
+    }
+
+    /// Returns the tracked buffer.
+    pub fn into_inner(self) -> RollingBuffer<T> {
+        self.buffer
+    }
+}
+
+/// Adds the newest element at logical index `index` to both deques, dropping the candidates it outlives.
+fn admit<T: PartialOrd>(buffer: &RollingBuffer<T>, mins: &mut VecDeque<u64>, maxs: &mut VecDeque<u64>, index: u64) {
+    let new = value(buffer, index);
+    if new.partial_cmp(new).is_none() {
+        return;
+    }
+    while mins.back().is_some_and(|&back| value(buffer, back) > new) {
+        mins.pop_back();
+    }
+    mins.push_back(index);
+    while maxs.back().is_some_and(|&back| value(buffer, back) < new) {
+        maxs.pop_back();
+    }
+    maxs.push_back(index);
+}
+
+/// The live element at a logical index taken from one of the deques.
+fn value<T>(buffer: &RollingBuffer<T>, index: u64) -> &T {
+    match buffer.get(index as usize) {
+        Some(value) => value,
+        None => unreachable!("the deques only hold live indices"),
+    }
+}
+
+impl<T> Deref for RollingMinMax<T> {
+    type Target = RollingBuffer<T>;
+
+    fn deref(&self) -> &RollingBuffer<T> {
+        &self.buffer
+    }
+}
@@ -1,2 +1,32 @@
+pub mod allocator;
+#[cfg(feature = "rkyv")]
+pub mod archived;
+pub mod array;
+#[allow(clippy::module_inception)]
 pub mod buffer;
+pub mod builder;
+#[cfg(feature = "defmt")]
+mod defmt_impl;
+pub mod deque;
+pub mod error;
+pub mod ext;
+#[cfg(feature = "heapless")]
+pub mod heapless;
+pub mod iter;
+#[cfg(feature = "serde")]
+pub mod logical_serde;
+pub mod minmax;
+#[cfg(feature = "rayon")]
+pub mod par_iter;
+#[cfg(feature = "proptest")]
+mod proptest_impl;
+#[cfg(feature = "quickcheck")]
+mod quickcheck_impl;
+mod ring;
+pub mod running;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod slots;
+pub mod stats;
+pub mod storage;
 pub mod traits;
\ No newline at end of file
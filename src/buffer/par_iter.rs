@@ -0,0 +1,58 @@
+use rayon::iter::{Chain, IntoParallelIterator, ParallelIterator};
+use rayon::slice;
+
+use super::buffer::RollingBuffer;
+
+/// Parallel iterator over the elements of a RollingBuffer in logical order.
+///
+/// The logical view is made of at most two contiguous runs of the underlying Vec, which rayon
+/// splits further on its own. It is an `IndexedParallelIterator`, so `enumerate()` yields logical
+/// positions relative to the oldest element.
+pub type ParIter<'a, T> = Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>;
+
+/// Mutable version of [`ParIter`].
+pub type ParIterMut<'a, T> = Chain<slice::IterMut<'a, T>, slice::IterMut<'a, T>>;
+
+impl<'a, T> IntoParallelIterator for &'a RollingBuffer<T>
+where
+    T: Sync,
+{
+    type Iter = ParIter<'a, T>;
+    type Item = &'a T;
+
+    fn into_par_iter(self) -> ParIter<'a, T> {
+        let (first, second) = self.as_slices();
+        first.into_par_iter().chain(second)
+    }
+}
+
+impl<'a, T> IntoParallelIterator for &'a mut RollingBuffer<T>
+where
+    T: Send,
+{
+    type Iter = ParIterMut<'a, T>;
+    type Item = &'a mut T;
+
+    fn into_par_iter(self) -> ParIterMut<'a, T> {
+        let (first, second) = self.as_mut_slices();
+        first.into_par_iter().chain(second)
+    }
+}
+
+impl<T> RollingBuffer<T> {
+    /// Returns a parallel iterator over the live elements in logical order.
+    pub fn par_iter(&self) -> ParIter<'_, T>
+    where
+        T: Sync,
+    {
+        self.into_par_iter()
+    }
+
+    /// Returns a parallel iterator over mutable references to the live elements in logical order.
+    pub fn par_iter_mut(&mut self) -> ParIterMut<'_, T>
+    where
+        T: Send,
+    {
+        self.into_par_iter()
+    }
+}
@@ -0,0 +1,52 @@
+use alloc::vec::Vec;
+use core::ops::{Range, RangeInclusive};
+
+use proptest::arbitrary::{Arbitrary, any_with};
+use proptest::collection::vec;
+use proptest::prop_oneof;
+use proptest::strategy::{BoxedStrategy, Just, Strategy};
+
+use super::buffer::RollingBuffer;
+
+/// Largest size of a generated buffer, wrapped buffers get up to 4 times as many pushes.
+const MAX_SIZE: usize = 16;
+
+/// Generates buffers by pushing the generated elements into a new buffer, so the eviction state
+/// comes from real pushes. Each regime is picked equally often: empty, unbounded (size 0),
+/// underfilled, exactly full and wrapped one to three times over. Shrinking stays within the regime
+/// and moves towards smaller sizes and fewer pushes, and towards the earlier regimes.
+impl<T> Arbitrary for RollingBuffer<T>
+where
+    T: Arbitrary + 'static,
+    T::Parameters: Clone + 'static,
+{
+    type Parameters = T::Parameters;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(params: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            (0..=MAX_SIZE).prop_map(RollingBuffer::new),
+            replayed::<T>(params.clone(), 0..=0, |_| 1..4 * MAX_SIZE),
+            replayed::<T>(params.clone(), 2..=MAX_SIZE, |size| 1..size),
+            replayed::<T>(params.clone(), 1..=MAX_SIZE, |size| size..size + 1),
+            replayed::<T>(params, 1..=MAX_SIZE, |size| size + 1..4 * size + 1),
+        ]
+        .boxed()
+    }
+}
+
+/// Buffers of a size from `sizes` with a number of pushes from `pushes(size)`.
+fn replayed<T>(params: T::Parameters, sizes: RangeInclusive<usize>, pushes: fn(usize) -> Range<usize>) -> BoxedStrategy<RollingBuffer<T>>
+where
+    T: Arbitrary + 'static,
+    T::Parameters: Clone + 'static,
+{
+    sizes
+        .prop_flat_map(move |size| (Just(size), vec(any_with::<T>(params.clone()), pushes(size))))
+        .prop_map(|(size, values): (usize, Vec<T>)| {
+            let mut buffer = RollingBuffer::new(size);
+            buffer.extend(values);
+            buffer
+        })
+        .boxed()
+}
@@ -0,0 +1,53 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use quickcheck::{Arbitrary, Gen};
+
+use super::buffer::RollingBuffer;
+
+/// Largest size of a generated buffer, wrapped buffers get up to 4 times as many pushes.
+const MAX_SIZE: usize = 16;
+
+/// Generates buffers by pushing the generated elements into a new buffer, so the eviction state
+/// comes from real pushes. Each regime is picked equally often: empty, unbounded (size 0),
+/// underfilled, exactly full and wrapped one to three times over.
+impl<T> Arbitrary for RollingBuffer<T>
+where
+    T: Arbitrary,
+{
+    fn arbitrary(g: &mut Gen) -> Self {
+        let size = usize::arbitrary(g) % MAX_SIZE + 1;
+        let (size, pushes) = match u8::arbitrary(g) % 5 {
+            0 => (size - 1, 0),
+            1 => (0, 1 + usize::arbitrary(g) % (4 * MAX_SIZE - 1)),
+            2 => {
+                let size = size.max(2);
+                (size, 1 + usize::arbitrary(g) % (size - 1))
+            }
+            3 => (size, size),
+            _ => (size, size + 1 + usize::arbitrary(g) % (3 * size)),
+        };
+        replay(size, (0..pushes).map(|_| T::arbitrary(g)).collect())
+    }
+
+    /// Evicted elements are gone, so the smaller buffers replay only the live ones: first without
+    /// the evictions, then into smaller sizes, then with fewer or smaller live elements.
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let size = self.size();
+        let live = self.to_vec();
+        let evictions = (self.total_pushed() > self.len() as u64).then(|| replay(size, live.clone()));
+        let sizes = size.shrink().map({
+            let live = live.clone();
+            move |size| replay(size, live.clone())
+        });
+        let pushes = live.shrink().map(move |values| replay(size, values));
+        Box::new(evictions.into_iter().chain(sizes).chain(pushes))
+    }
+}
+
+/// A new buffer of the given size with every value pushed in order.
+fn replay<T>(size: usize, values: Vec<T>) -> RollingBuffer<T> {
+    let mut buffer = RollingBuffer::new(size);
+    buffer.extend(values);
+    buffer
+}
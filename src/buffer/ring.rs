@@ -0,0 +1,48 @@
+use core::cmp::Ordering;
+
+/// Index of the k-th live element of a ring of `size` slots whose oldest element is at `head`, k may be at most `size`.
+pub(crate) fn slot(head: usize, k: usize, size: usize) -> usize {
+    let index = head + k;
+    if index >= size { index - size } else { index }
+}
+
+/// Position of logical index `i` among the `len` live elements after `count` pushes, 0 being the oldest one.
+/// Otherwise `Ordering::Less` if it was evicted or `Ordering::Greater` if it hasn't been pushed yet.
+/// Uses wrapping arithmetic, so it keeps working once the logical indices wrapped around `usize::MAX`.
+pub(crate) fn window_offset(count: u64, len: usize, i: usize) -> Result<usize, Ordering> {
+    let end = count as usize;
+    let start = end.wrapping_sub(len);
+    let k = i.wrapping_sub(start);
+    if k < len {
+        Ok(k)
+    } else if start <= end {
+        Err(if i < start { Ordering::Less } else { Ordering::Greater })
+    } else if i - end < start - i {
+        // the window straddles the wrap point and `i` lies in the gap, closer to the newest end
+        Err(Ordering::Greater)
+    } else {
+        Err(Ordering::Less)
+    }
+}
+
+/// The `len` live elements starting at `head` as at most two runs, oldest first. A `size` of 0 never wraps.
+pub(crate) fn as_slices<T>(slots: &[T], head: usize, len: usize, size: usize) -> (&[T], &[T]) {
+    let end = head + len;
+    if size == 0 || end <= size {
+        (&slots[head..end], &[])
+    } else {
+        let (wrapped, oldest) = slots.split_at(head);
+        (oldest, &wrapped[..end - size])
+    }
+}
+
+/// Mutable version of `as_slices()`.
+pub(crate) fn as_mut_slices<T>(slots: &mut [T], head: usize, len: usize, size: usize) -> (&mut [T], &mut [T]) {
+    let end = head + len;
+    if size == 0 || end <= size {
+        (&mut slots[head..end], &mut [])
+    } else {
+        let (wrapped, oldest) = slots.split_at_mut(head);
+        (oldest, &mut wrapped[..end - size])
+    }
+}
@@ -0,0 +1,469 @@
+use core::ops::Deref;
+
+use super::buffer::RollingBuffer;
+use super::stats::{self, RollingStats, Sample};
+
+/// RollingSum owns a RollingBuffer and keeps the sum of its live window up to date on every push:
+/// the new value is added and the evicted one taken out, so `sum()` and `mean()` are O(1).
+/// Nothing is taken out while the buffer fills up. The evicted value is read from the buffer
+/// before the push, so this works for buffers that don't track `last_removed` as well.
+///
+/// Integer sums are exact. Float sums pick up rounding errors with every update that the window
+/// doesn't forget, set `recompute_every()` to replace the running sum with a compensated sum
+/// of the window every so many pushes.
+///
+/// ['buffer']: buffer is the summed window
+/// ['sum']: sum is the running sum of the live elements
+/// ['recompute_every']: recompute_every is the number of pushes between exact recomputations, 0 for never
+/// ['pushes']: pushes is the number of pushes since the last recomputation
+#[derive(Debug, Clone)]
+pub struct RollingSum<T>
+where
+    T: Sample,
+{
+    buffer: RollingBuffer<T>,
+    sum: T::Sum,
+    recompute_every: usize,
+    pushes: usize,
+}
+
+impl<T> RollingSum<T>
+where
+    T: Sample,
+{
+    /// Creates an empty running sum over a new RollingBuffer of the given size, 0 for an unbounded one.
+    pub fn new(size: usize) -> Self {
+        Self::from_buffer(RollingBuffer::new(size))
+    }
+
+    /// Sums an existing buffer, starting from the exact sum of its live elements.
+    pub fn from_buffer(buffer: RollingBuffer<T>) -> Self {
+        Self {
+            sum: T::sum(buffer.iter()),
+            buffer,
+            recompute_every: 0,
+            pushes: 0,
+        }
+    }
+
+    /// Recomputes the sum from the window every `pushes` pushes, 0 (the default) turns it off.
+    /// Once per window size bounds the drift of a float sum to that of a single pass at O(1) amortized cost.
+    pub fn recompute_every(mut self, pushes: usize) -> Self {
+        self.recompute_every = pushes;
+        self
+    }
+
+    /// Pushes into the buffer and updates the sum.
+    pub fn push(&mut self, value: T) {
+        let evicted = if self.buffer.is_full() { self.buffer.first().copied() } else { None };
+        self.buffer.push(value);
+        self.sum = T::add(self.sum, value);
+        if let Some(evicted) = evicted {
+            self.sum = T::sub(self.sum, evicted);
+        }
+        self.pushes += 1;
+        if self.recompute_every > 0 && self.pushes >= self.recompute_every {
+            self.recompute();
+        }
+    }
+
+    /// Replaces the running sum with the sum of the live elements, same as `RollingStats::sum()`.
+    pub fn recompute(&mut self) {
+        self.sum = T::sum(self.buffer.iter());
+        self.pushes = 0;
+    }
+
+    /// Sum of the live elements, 0 for an empty buffer.
+    pub fn sum(&self) -> T::Sum {
+        self.sum
+    }
+
+    /// Arithmetic mean of the live elements, None for an empty buffer.
+    pub fn mean(&self) -> Option<f64> {
+        if self.buffer.is_empty() {
+            return None;
+        }
+        Some(T::sum_to_f64(self.sum) / self.buffer.len() as f64)
+    }
+
+    /// Empties the buffer as `RollingWrite::clear()` does.
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.sum = T::ZERO;
+        self.pushes = 0;
+    }
+
+    /// Returns the summed buffer.
+    pub fn into_inner(self) -> RollingBuffer<T> {
+        self.buffer
+    }
+}
+
+impl<T> Deref for RollingSum<T>
+where
+    T: Sample,
+{
+    type Target = RollingBuffer<T>;
+
+    fn deref(&self) -> &RollingBuffer<T> {
+        &self.buffer
+    }
+}
+
+/// RollingVariance owns a RollingBuffer and keeps the mean and variance of its live window up to date
+/// on every push with Welford's update: pushing into a buffer that fills up adds the value, pushing into
+/// a full one replaces the evicted value in a single step. `mean()`, `variance()` and `std_dev()` are O(1),
+/// for mean ± kσ thresholds that are checked on every push.
+///
+/// Like `RollingStats::variance()` this is the population variance, 0 for a single element.
+/// The update works on deviations from the running mean, so it stays accurate for large values
+/// with a small spread, but it still picks up rounding errors with every update; set `recompute_every()`
+/// to replace both with a two-pass computation of the window every so many pushes.
+///
+/// ['buffer']: buffer is the window
+/// ['mean']: mean is the running mean of the live elements, 0 for an empty buffer
+/// ['m2']: m2 is the running sum of squared deviations from the mean
+/// ['recompute_every']: recompute_every is the number of pushes between exact recomputations, 0 for never
+/// ['pushes']: pushes is the number of pushes since the last recomputation
+#[derive(Debug, Clone)]
+pub struct RollingVariance<T>
+where
+    T: Sample,
+{
+    buffer: RollingBuffer<T>,
+    mean: f64,
+    m2: f64,
+    recompute_every: usize,
+    pushes: usize,
+}
+
+impl<T> RollingVariance<T>
+where
+    T: Sample,
+{
+    /// Creates an empty running variance over a new RollingBuffer of the given size, 0 for an unbounded one.
+    pub fn new(size: usize) -> Self {
+        Self::from_buffer(RollingBuffer::new(size))
+    }
+
+    /// Tracks an existing buffer, starting from a two-pass computation of its live elements.
+    pub fn from_buffer(buffer: RollingBuffer<T>) -> Self {
+        let mut running = Self {
+            buffer,
+            mean: 0.0,
+            m2: 0.0,
+            recompute_every: 0,
+            pushes: 0,
+        };
+        running.recompute();
+        running
+    }
+
+    /// Recomputes the mean and variance from the window every `pushes` pushes, 0 (the default) turns it off.
+    pub fn recompute_every(mut self, pushes: usize) -> Self {
+        self.recompute_every = pushes;
+        self
+    }
+
+    /// Pushes into the buffer and updates the mean and variance.
+    pub fn push(&mut self, value: T) {
+        let evicted = if self.buffer.is_full() { self.buffer.first().copied() } else { None };
+        self.buffer.push(value);
+        let new = value.to_f64();
+        let len = self.buffer.len() as f64;
+        match evicted {
+            Some(evicted) => {
+                let old = evicted.to_f64();
+                let mean = self.mean + (new - old) / len;
+                self.m2 += (new - old) * (new - mean + old - self.mean);
+                self.mean = mean;
+            }
+            None => {
+                let delta = new - self.mean;
+                self.mean += delta / len;
+                self.m2 += delta * (new - self.mean);
+            }
+        }
+        self.m2 = f64::max(self.m2, 0.0);
+        self.pushes += 1;
+        if self.recompute_every > 0 && self.pushes >= self.recompute_every {
+            self.recompute();
+        }
+    }
+
+    /// Replaces the running mean and variance with those of the live elements,
+    /// same as `RollingStats::mean()` and `variance()`.
+    pub fn recompute(&mut self) {
+        self.mean = self.buffer.mean().unwrap_or(0.0);
+        self.m2 = self.buffer.variance().unwrap_or(0.0) * self.buffer.len() as f64;
+        self.pushes = 0;
+    }
+
+    /// Arithmetic mean of the live elements, None for an empty buffer.
+    pub fn mean(&self) -> Option<f64> {
+        if self.buffer.is_empty() {
+            return None;
+        }
+        Some(self.mean)
+    }
+
+    /// Population variance of the live elements, 0 for a single element and None for an empty buffer.
+    pub fn variance(&self) -> Option<f64> {
+        if self.buffer.is_empty() {
+            return None;
+        }
+        Some(self.m2 / self.buffer.len() as f64)
+    }
+
+    /// Square root of `variance()`. Needs the `std` feature for `f64::sqrt`.
+    #[cfg(feature = "std")]
+    pub fn std_dev(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+
+    /// Empties the buffer as `RollingWrite::clear()` does.
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.mean = 0.0;
+        self.m2 = 0.0;
+        self.pushes = 0;
+    }
+
+    /// Returns the buffer.
+    pub fn into_inner(self) -> RollingBuffer<T> {
+        self.buffer
+    }
+}
+
+impl<T> Deref for RollingVariance<T>
+where
+    T: Sample,
+{
+    type Target = RollingBuffer<T>;
+
+    fn deref(&self) -> &RollingBuffer<T> {
+        &self.buffer
+    }
+}
+
+/// RollingRms owns a RollingBuffer and keeps the sum of the squares of its live window up to date on every push,
+/// like RollingSum does for the sum, so `mean_square()` and `rms()` are O(1) for metering at high sample rates.
+///
+/// The running sum of squares picks up rounding errors with every update and never drops below 0;
+/// set `recompute_every()` to replace it with a compensated sum over the window every so many pushes.
+///
+/// ['buffer']: buffer is the window
+/// ['squares']: squares is the running sum of the squares of the live elements
+/// ['recompute_every']: recompute_every is the number of pushes between exact recomputations, 0 for never
+/// ['pushes']: pushes is the number of pushes since the last recomputation
+#[derive(Debug, Clone)]
+pub struct RollingRms<T>
+where
+    T: Sample,
+{
+    buffer: RollingBuffer<T>,
+    squares: f64,
+    recompute_every: usize,
+    pushes: usize,
+}
+
+impl<T> RollingRms<T>
+where
+    T: Sample,
+{
+    /// Creates an empty running sum of squares over a new RollingBuffer of the given size, 0 for an unbounded one.
+    pub fn new(size: usize) -> Self {
+        Self::from_buffer(RollingBuffer::new(size))
+    }
+
+    /// Tracks an existing buffer, starting from the compensated sum of the squares of its live elements.
+    pub fn from_buffer(buffer: RollingBuffer<T>) -> Self {
+        let mut running = Self {
+            buffer,
+            squares: 0.0,
+            recompute_every: 0,
+            pushes: 0,
+        };
+        running.recompute();
+        running
+    }
+
+    /// Recomputes the sum of squares from the window every `pushes` pushes, 0 (the default) turns it off.
+    pub fn recompute_every(mut self, pushes: usize) -> Self {
+        self.recompute_every = pushes;
+        self
+    }
+
+    /// Pushes into the buffer and updates the sum of squares.
+    pub fn push(&mut self, value: T) {
+        let evicted = if self.buffer.is_full() { self.buffer.first().copied() } else { None };
+        self.buffer.push(value);
+        self.squares += value.to_f64() * value.to_f64();
+        if let Some(evicted) = evicted {
+            self.squares = f64::max(self.squares - evicted.to_f64() * evicted.to_f64(), 0.0);
+        }
+        self.pushes += 1;
+        if self.recompute_every > 0 && self.pushes >= self.recompute_every {
+            self.recompute();
+        }
+    }
+
+    /// Replaces the running sum of squares with the compensated sum over the live elements.
+    pub fn recompute(&mut self) {
+        self.squares = stats::compensated_sum(self.buffer.iter().map(|value| value.to_f64() * value.to_f64()));
+        self.pushes = 0;
+    }
+
+    /// Mean of the squares of the live elements, None for an empty buffer.
+    pub fn mean_square(&self) -> Option<f64> {
+        if self.buffer.is_empty() {
+            return None;
+        }
+        Some(self.squares / self.buffer.len() as f64)
+    }
+
+    /// Root mean square of the live elements, None for an empty buffer. Needs the `std` feature for `f64::sqrt`.
+    #[cfg(feature = "std")]
+    pub fn rms(&self) -> Option<f64> {
+        self.mean_square().map(f64::sqrt)
+    }
+
+    /// Empties the buffer as `RollingWrite::clear()` does.
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.squares = 0.0;
+        self.pushes = 0;
+    }
+
+    /// Returns the buffer.
+    pub fn into_inner(self) -> RollingBuffer<T> {
+        self.buffer
+    }
+}
+
+impl<T> Deref for RollingRms<T>
+where
+    T: Sample,
+{
+    type Target = RollingBuffer<T>;
+
+    fn deref(&self) -> &RollingBuffer<T> {
+        &self.buffer
+    }
+}
+
+/// RollingEma owns a RollingBuffer and keeps an exponential moving average of everything pushed into it,
+/// with a smoothing factor `alpha` in (0, 1]: every push gives the new value a weight of `alpha`
+/// and multiplies the weight of all older values by `1 - alpha`. The average covers the whole stream,
+/// not only the live window, the buffer keeps the recent values next to it.
+///
+/// Warm-up: instead of seeding the average with the first value, which then dominates it for a long
+/// time, `value()` is the weighted mean of the values pushed so far with exactly these weights,
+/// divided by the sum of the weights. The first value is returned as is, and as more values arrive
+/// this converges to the usual recursive EMA. `is_warmed_up()` tells whether the effective span
+/// of `2 / alpha - 1` values has been seen, before that the average rests on fewer values than it is meant to.
+///
+/// ['buffer']: buffer is the window
+/// ['alpha']: alpha is the smoothing factor
+/// ['weighted']: weighted is the sum of the values multiplied by their weights
+/// ['weight']: weight is the sum of the weights
+/// ['samples']: samples is the number of values the average covers
+#[derive(Debug, Clone)]
+pub struct RollingEma<T>
+where
+    T: Sample,
+{
+    buffer: RollingBuffer<T>,
+    alpha: f64,
+    weighted: f64,
+    weight: f64,
+    samples: u64,
+}
+
+impl<T> RollingEma<T>
+where
+    T: Sample,
+{
+    /// Creates an empty average over a new RollingBuffer of the given size, 0 for an unbounded one.
+    ///
+    /// Panics if `alpha` is not in (0, 1].
+    pub fn new(size: usize, alpha: f64) -> Self {
+        Self::from_buffer(RollingBuffer::new(size), alpha)
+    }
+
+    /// Attaches an average to an existing buffer, initialized by feeding it the live elements oldest first.
+    /// Evicted elements are gone, so the average starts out from the current window only.
+    ///
+    /// Panics if `alpha` is not in (0, 1].
+    pub fn from_buffer(buffer: RollingBuffer<T>, alpha: f64) -> Self {
+        assert!(alpha > 0.0 && alpha <= 1.0, "the smoothing factor {alpha} is not in (0, 1]");
+        let (weighted, weight) = buffer.iter().fold((0.0, 0.0), |sums, &value| decay(alpha, sums, value));
+        Self {
+            samples: buffer.len() as u64,
+            buffer,
+            alpha,
+            weighted,
+            weight,
+        }
+    }
+
+    /// Pushes into the buffer and updates the average.
+    pub fn push(&mut self, value: T) {
+        self.buffer.push(value);
+        (self.weighted, self.weight) = decay(self.alpha, (self.weighted, self.weight), value);
+        self.samples += 1;
+    }
+
+    /// Current average, None before the first push.
+    pub fn value(&self) -> Option<f64> {
+        if self.samples == 0 {
+            return None;
+        }
+        Some(self.weighted / self.weight)
+    }
+
+    /// The smoothing factor.
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Number of values the average covers.
+    pub fn samples(&self) -> u64 {
+        self.samples
+    }
+
+    /// Whether at least `2 / alpha - 1` values were pushed, the span of the simple moving average
+    /// whose values have the same mean age.
+    pub fn is_warmed_up(&self) -> bool {
+        self.samples as f64 >= 2.0 / self.alpha - 1.0
+    }
+
+    /// Empties the buffer as `RollingWrite::clear()` does and restarts the average.
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.weighted = 0.0;
+        self.weight = 0.0;
+        self.samples = 0;
+    }
+
+    /// Returns the buffer.
+    pub fn into_inner(self) -> RollingBuffer<T> {
+        self.buffer
+    }
+}
+
+/// Adds a value with weight 1 to the weighted sum and the sum of the weights, after decaying the older ones.
+fn decay<T: Sample>(alpha: f64, (weighted, weight): (f64, f64), value: T) -> (f64, f64) {
+    (value.to_f64() + (1.0 - alpha) * weighted, 1.0 + (1.0 - alpha) * weight)
+}
+
+impl<T> Deref for RollingEma<T>
+where
+    T: Sample,
+{
+    type Target = RollingBuffer<T>;
+
+    fn deref(&self) -> &RollingBuffer<T> {
+        &self.buffer
+    }
+}
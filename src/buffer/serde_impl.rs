@@ -0,0 +1,60 @@
+use alloc::vec::Vec;
+
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+use super::buffer::RollingBuffer;
+use super::logical_serde::LogicalSerde;
+
+/// Serializes the logical state: `size`, `count`, whether evictions are tracked, `last_removed`
+/// and the live elements in logical order as `data`. The physical rotation is not part of it,
+/// deserializing lays the elements out the way `count` pushes would have.
+impl<T> Serialize for RollingBuffer<T>
+where
+    T: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("RollingBuffer", 5)?;
+        state.serialize_field("size", &self.size())?;
+        state.serialize_field("count", &self.total_pushed())?;
+        state.serialize_field("track_removed", &self.tracks_removed())?;
+        state.serialize_field("last_removed", self.last_removed())?;
+        state.serialize_field("data", &LogicalSerde(self))?;
+        state.end()
+    }
+}
+
+/// The serialized form, validated before it is turned into a buffer.
+#[derive(serde::Deserialize)]
+#[serde(rename = "RollingBuffer", bound = "T: Deserialize<'de>")]
+struct State<T> {
+    size: usize,
+    count: u64,
+    track_removed: bool,
+    last_removed: Option<T>,
+    data: Vec<T>,
+}
+
+/// Restores a buffer serialized by the `Serialize` impl, so that it returns the same `get()` results
+/// and evicts the same element on the next push. Fails with a deserialization error instead of
+/// panicking if a fixed size buffer holds more than `size` elements or `count` is below their number.
+impl<'de, T> Deserialize<'de> for RollingBuffer<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let State { size, count, track_removed, last_removed, data } = State::deserialize(deserializer)?;
+        if size > 0 && data.len() > size {
+            return Err(de::Error::custom(format_args!(
+                "{} live elements don't fit into a buffer of size {size}",
+                data.len()
+            )));
+        }
+        if count < data.len() as u64 {
+            return Err(de::Error::custom(format_args!("count {count} is below the {} live elements", data.len())));
+        }
+        let mut buffer = RollingBuffer::from_newest(size, data, count, last_removed);
+        buffer.set_track_removed(track_removed);
+        Ok(buffer)
+    }
+}
@@ -0,0 +1,99 @@
+use core::mem;
+use core::ops::{Deref, DerefMut};
+
+use super::allocator::{AllocSlice, AllocVec, Allocator};
+
+/// Storage of a RollingBuffer. A buffer that isn't full stores its live elements, oldest first, in a Vec
+/// that grows on push. Once all `size` slots are live they move into a boxed slice of exactly `size` elements,
+/// which can't grow by accident and doesn't carry a capacity. An unbounded buffer never fills up and keeps the Vec.
+/// Both variants deref to the slots as a slice.
+#[derive(Clone)]
+pub(crate) enum Slots<T, A: Allocator> {
+    Growing(AllocVec<T, A>),
+    Full(AllocSlice<T, A>),
+    /// Only there while the storage is moved from one variant into the other.
+    Switching,
+}
+
+impl<T, A: Allocator> Slots<T, A> {
+    /// Takes the elements of `vec` as the slots of a buffer of the given size, boxed if they fill it.
+    pub(crate) fn new(vec: AllocVec<T, A>, size: usize) -> Self {
+        let mut slots = Slots::Growing(vec);
+        slots.settle(size);
+        slots
+    }
+
+    /// Returns the slots as a Vec, turning a boxed slice back into one first. The allocation is reused.
+    pub(crate) fn vec(&mut self) -> &mut AllocVec<T, A> {
+        if let Slots::Full(_) = self {
+            *self = match mem::replace(self, Slots::Switching) {
+                Slots::Full(slots) => Slots::Growing(slots.into_vec()),
+                slots => slots,
+            };
+        }
+        match self {
+            Slots::Growing(vec) => vec,
+            _ => unreachable!("the slots were just turned into a Vec"),
+        }
+    }
+
+    /// Consumes the slots and returns them as a Vec.
+    pub(crate) fn into_vec(mut self) -> AllocVec<T, A> {
+        self.vec();
+        match self {
+            Slots::Growing(vec) => vec,
+            _ => unreachable!("the slots were just turned into a Vec"),
+        }
+    }
+
+    /// Moves the Vec into a boxed slice once it holds all slots of a fixed size buffer.
+    /// Spare capacity left over from growing is released, which may reallocate.
+    pub(crate) fn settle(&mut self, size: usize) {
+        if size > 0 && self.len() == size && let Slots::Growing(_) = self {
+            *self = match mem::replace(self, Slots::Switching) {
+                Slots::Growing(vec) => Slots::Full(vec.into_boxed_slice()),
+                slots => slots,
+            };
+        }
+    }
+
+    /// Capacity of the Vec, or the number of slots once they are boxed.
+    pub(crate) fn capacity(&self) -> usize {
+        match self {
+            Slots::Growing(vec) => vec.capacity(),
+            _ => self.len(),
+        }
+    }
+
+    /// Returns the allocator the slots are stored in.
+    #[cfg(feature = "allocator_api")]
+    pub(crate) fn allocator(&self) -> &A {
+        match self {
+            Slots::Growing(vec) => vec.allocator(),
+            Slots::Full(slots) => alloc::boxed::Box::allocator(slots),
+            Slots::Switching => unreachable!("the slots are only switching within a method"),
+        }
+    }
+}
+
+impl<T, A: Allocator> Deref for Slots<T, A> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        match self {
+            Slots::Growing(vec) => vec,
+            Slots::Full(slots) => slots,
+            Slots::Switching => &[],
+        }
+    }
+}
+
+impl<T, A: Allocator> DerefMut for Slots<T, A> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        match self {
+            Slots::Growing(vec) => vec,
+            Slots::Full(slots) => slots,
+            Slots::Switching => &mut [],
+        }
+    }
+}
@@ -0,0 +1,848 @@
+use alloc::collections::BinaryHeap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::{Ordering, Reverse};
+use core::{fmt, mem};
+
+use super::iter::Iter;
+use super::traits::RollingRead;
+
+/// Numeric element types the statistics are computed for: the primitive integers and floats.
+pub trait Sample: Copy + PartialOrd {
+    /// Accumulator of `sum()`: i128 or u128 for integers so that no window can overflow it, f64 for floats.
+    type Sum: Copy + fmt::Debug + PartialEq;
+
+    /// The sum of no values.
+    const ZERO: Self::Sum;
+
+    /// The value as an f64, rounded for 64-bit integers above 2^53.
+    fn to_f64(self) -> f64;
+
+    /// Sums the values in the accumulator, floats with compensated (Neumaier) summation.
+    fn sum(values: Iter<'_, Self>) -> Self::Sum;
+
+    /// The accumulated sum as an f64.
+    fn sum_to_f64(sum: Self::Sum) -> f64;
+
+    /// Adds a value to a running sum.
+    fn add(sum: Self::Sum, value: Self) -> Self::Sum;
+
+    /// Takes a value that was added before out of a running sum.
+    fn sub(sum: Self::Sum, value: Self) -> Self::Sum;
+}
+
+/// Neumaier's variant of Kahan summation: the rounding error of every addition is kept in a second
+/// accumulator, so cancelling terms like `[1e16, 1.0, -1e16]` still sum to 1.0.
+pub(crate) fn compensated_sum(values: impl Iterator<Item = f64>) -> f64 {
+    let mut sum = Compensated::default();
+    values.for_each(|value| sum.add(value));
+    sum.total()
+}
+
+/// Running state of `compensated_sum()`, to sum several series in the same pass.
+#[derive(Default)]
+struct Compensated {
+    sum: f64,
+    compensation: f64,
+}
+
+impl Compensated {
+    fn add(&mut self, value: f64) {
+        let total = self.sum + value;
+        if f64::abs(self.sum) >= f64::abs(value) {
+            self.compensation += (self.sum - total) + value;
+        } else {
+            self.compensation += (value - total) + self.sum;
+        }
+        self.sum = total;
+    }
+
+    fn total(&self) -> f64 {
+        self.sum + self.compensation
+    }
+}
+
+impl Sample for i8 {
+    type Sum = i128;
+
+    const ZERO: i128 = 0;
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn sum(values: Iter<'_, Self>) -> i128 {
+        values.map(|&value| value as i128).sum()
+    }
+
+    fn sum_to_f64(sum: i128) -> f64 {
+        sum as f64
+    }
+
+    fn add(sum: i128, value: Self) -> i128 {
+        sum + value as i128
+    }
+
+    fn sub(sum: i128, value: Self) -> i128 {
+        sum - value as i128
+    }
+}
+
+impl Sample for i16 {
+    type Sum = i128;
+
+    const ZERO: i128 = 0;
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn sum(values: Iter<'_, Self>) -> i128 {
+        values.map(|&value| value as i128).sum()
+    }
+
+    fn sum_to_f64(sum: i128) -> f64 {
+        sum as f64
+    }
+
+    fn add(sum: i128, value: Self) -> i128 {
+        sum + value as i128
+    }
+
+    fn sub(sum: i128, value: Self) -> i128 {
+        sum - value as i128
+    }
+}
+
+impl Sample for i32 {
+    type Sum = i128;
+
+    const ZERO: i128 = 0;
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn sum(values: Iter<'_, Self>) -> i128 {
+        values.map(|&value| value as i128).sum()
+    }
+
+    fn sum_to_f64(sum: i128) -> f64 {
+        sum as f64
+    }
+
+    fn add(sum: i128, value: Self) -> i128 {
+        sum + value as i128
+    }
+
+    fn sub(sum: i128, value: Self) -> i128 {
+        sum - value as i128
+    }
+}
+
+impl Sample for i64 {
+    type Sum = i128;
+
+    const ZERO: i128 = 0;
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn sum(values: Iter<'_, Self>) -> i128 {
+        values.map(|&value| value as i128).sum()
+    }
+
+    fn sum_to_f64(sum: i128) -> f64 {
+        sum as f64
+    }
+
+    fn add(sum: i128, value: Self) -> i128 {
+        sum + value as i128
+    }
+
+    fn sub(sum: i128, value: Self) -> i128 {
+        sum - value as i128
+    }
+}
+
+impl Sample for isize {
+    type Sum = i128;
+
+    const ZERO: i128 = 0;
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn sum(values: Iter<'_, Self>) -> i128 {
+        values.map(|&value| value as i128).sum()
+    }
+
+    fn sum_to_f64(sum: i128) -> f64 {
+        sum as f64
+    }
+
+    fn add(sum: i128, value: Self) -> i128 {
+        sum + value as i128
+    }
+
+    fn sub(sum: i128, value: Self) -> i128 {
+        sum - value as i128
+    }
+}
+
+impl Sample for u8 {
+    type Sum = u128;
+
+    const ZERO: u128 = 0;
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn sum(values: Iter<'_, Self>) -> u128 {
+        values.map(|&value| value as u128).sum()
+    }
+
+    fn sum_to_f64(sum: u128) -> f64 {
+        sum as f64
+    }
+
+    fn add(sum: u128, value: Self) -> u128 {
+        sum + value as u128
+    }
+
+    fn sub(sum: u128, value: Self) -> u128 {
+        sum - value as u128
+    }
+}
+
+impl Sample for u16 {
+    type Sum = u128;
+
+    const ZERO: u128 = 0;
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn sum(values: Iter<'_, Self>) -> u128 {
+        values.map(|&value| value as u128).sum()
+    }
+
+    fn sum_to_f64(sum: u128) -> f64 {
+        sum as f64
+    }
+
+    fn add(sum: u128, value: Self) -> u128 {
+        sum + value as u128
+    }
+
+    fn sub(sum: u128, value: Self) -> u128 {
+        sum - value as u128
+    }
+}
+
+impl Sample for u32 {
+    type Sum = u128;
+
+    const ZERO: u128 = 0;
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn sum(values: Iter<'_, Self>) -> u128 {
+        values.map(|&value| value as u128).sum()
+    }
+
+    fn sum_to_f64(sum: u128) -> f64 {
+        sum as f64
+    }
+
+    fn add(sum: u128, value: Self) -> u128 {
+        sum + value as u128
+    }
+
+    fn sub(sum: u128, value: Self) -> u128 {
+        sum - value as u128
+    }
+}
+
+impl Sample for u64 {
+    type Sum = u128;
+
+    const ZERO: u128 = 0;
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn sum(values: Iter<'_, Self>) -> u128 {
+        values.map(|&value| value as u128).sum()
+    }
+
+    fn sum_to_f64(sum: u128) -> f64 {
+        sum as f64
+    }
+
+    fn add(sum: u128, value: Self) -> u128 {
+        sum + value as u128
+    }
+
+    fn sub(sum: u128, value: Self) -> u128 {
+        sum - value as u128
+    }
+}
+
+impl Sample for usize {
+    type Sum = u128;
+
+    const ZERO: u128 = 0;
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn sum(values: Iter<'_, Self>) -> u128 {
+        values.map(|&value| value as u128).sum()
+    }
+
+    fn sum_to_f64(sum: u128) -> f64 {
+        sum as f64
+    }
+
+    fn add(sum: u128, value: Self) -> u128 {
+        sum + value as u128
+    }
+
+    fn sub(sum: u128, value: Self) -> u128 {
+        sum - value as u128
+    }
+}
+
+impl Sample for f32 {
+    type Sum = f64;
+
+    const ZERO: f64 = 0.0;
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn sum(values: Iter<'_, Self>) -> f64 {
+        compensated_sum(values.map(|&value| value as f64))
+    }
+
+    fn sum_to_f64(sum: f64) -> f64 {
+        sum
+    }
+
+    fn add(sum: f64, value: Self) -> f64 {
+        sum + value as f64
+    }
+
+    fn sub(sum: f64, value: Self) -> f64 {
+        sum - value as f64
+    }
+}
+
+impl Sample for f64 {
+    type Sum = f64;
+
+    const ZERO: f64 = 0.0;
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn sum(values: Iter<'_, Self>) -> f64 {
+        compensated_sum(values.copied())
+    }
+
+    fn sum_to_f64(sum: f64) -> f64 {
+        sum
+    }
+
+    fn add(sum: f64, value: Self) -> f64 {
+        sum + value
+    }
+
+    fn sub(sum: f64, value: Self) -> f64 {
+        sum - value
+    }
+}
+
+/// A live element with its logical index, as yielded by `RollingBuffer::iter_indexed()`.
+pub type Indexed<'a, T> = (usize, &'a T);
+
+/// The first of the values that compares as `wanted` against all others, with its logical index.
+/// Values that don't compare with themselves, like NaN, are skipped.
+fn extreme<T: PartialOrd>(values: Iter<'_, T>, start: usize, wanted: Ordering) -> Option<Indexed<'_, T>> {
+    let mut best: Option<Indexed<'_, T>> = None;
+    for (k, value) in values.enumerate() {
+        if value.partial_cmp(value).is_none() {
+            continue;
+        }
+        if best.is_none_or(|(_, best)| value.partial_cmp(best) == Some(wanted)) {
+            best = Some((start.wrapping_add(k), value));
+        }
+    }
+    best
+}
+
+/// Population variance of the values around their `mean`: the mean of the squared deviations, with the
+/// rounding error of `mean` taken out by subtracting the squared mean deviation (the corrected two-pass algorithm).
+/// Computing `mean` is the first pass, the deviations and their squares are summed together in the second one.
+fn variance_around<'a, T: Sample + 'a>(values: impl ExactSizeIterator<Item = &'a T>, mean: f64) -> f64 {
+    let len = values.len() as f64;
+    let mut deviations = Compensated::default();
+    let mut squares = Compensated::default();
+    for value in values {
+        let deviation = value.to_f64() - mean;
+        deviations.add(deviation);
+        squares.add(deviation * deviation);
+    }
+    f64::max((squares.total() - deviations.total() * deviations.total() / len) / len, 0.0)
+}
+
+/// A candidate of `top()`, ranked by its key and then by its age, older ranking higher.
+struct Ranked<'a, T, K> {
+    key: K,
+    position: usize,
+    value: &'a T,
+}
+
+impl<T, K: Ord> Ord for Ranked<'_, T, K> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key).then(other.position.cmp(&self.position))
+    }
+}
+
+impl<T, K: Ord> PartialOrd for Ranked<'_, T, K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T, K: Ord> PartialEq for Ranked<'_, T, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T, K: Ord> Eq for Ranked<'_, T, K> {}
+
+/// The `k` values with the largest keys, largest first and equal keys oldest first, keeping the `k` best
+/// candidates seen so far in a min-heap: O(n log k) time and O(k) memory.
+fn top<'a, T, K: Ord>(values: Iter<'a, T>, k: usize, mut key: impl FnMut(&'a T) -> K) -> Vec<&'a T> {
+    let mut heap = BinaryHeap::with_capacity(k.min(values.len()));
+    for (position, value) in values.enumerate() {
+        let candidate = Reverse(Ranked { key: key(value), position, value });
+        if heap.len() < k {
+            heap.push(candidate);
+        } else if let Some(mut worst) = heap.peek_mut()
+            && candidate < *worst
+        {
+            *worst = candidate;
+        }
+    }
+    heap.into_sorted_vec().into_iter().map(|Reverse(ranked)| ranked.value).collect()
+}
+
+/// Histogram of the live elements returned by `RollingStats::histogram()` and `histogram_with_edges()`.
+/// Bin `i` counts the elements `x` with `edges[i] <= x < edges[i + 1]`, the last bin also includes
+/// its upper edge. Elements below the first edge or above the last one are counted as underflow
+/// or overflow, NaN is not counted at all.
+///
+/// ['edges']: edges holds the bin boundaries in increasing order, one more than there are bins
+/// ['counts']: counts holds the number of elements in each bin
+/// ['underflow']: underflow is the number of elements below the first edge
+/// ['overflow']: overflow is the number of elements above the last edge
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    edges: Vec<f64>,
+    counts: Vec<usize>,
+    underflow: usize,
+    overflow: usize,
+}
+
+impl Histogram {
+    /// Counts the values into the bins between `edges`, which must be at least two and strictly increasing.
+    fn count(values: impl Iterator<Item = f64>, edges: Vec<f64>) -> Self {
+        let mut histogram = Self {
+            counts: vec![0; edges.len() - 1],
+            edges,
+            underflow: 0,
+            overflow: 0,
+        };
+        let (bins, last) = (histogram.counts.len(), histogram.edges[histogram.counts.len()]);
+        for value in values.filter(|value| !value.is_nan()) {
+            let above = histogram.edges.partition_point(|&edge| edge <= value);
+            if above == 0 {
+                histogram.underflow += 1;
+            } else if above <= bins {
+                histogram.counts[above - 1] += 1;
+            } else if value == last {
+                histogram.counts[bins - 1] += 1;
+            } else {
+                histogram.overflow += 1;
+            }
+        }
+        histogram
+    }
+
+    /// The bin boundaries, one more than there are bins.
+    pub fn edges(&self) -> &[f64] {
+        &self.edges
+    }
+
+    /// The number of elements in each bin.
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+
+    /// The number of elements below the first edge.
+    pub fn underflow(&self) -> usize {
+        self.underflow
+    }
+
+    /// The number of elements above the last edge.
+    pub fn overflow(&self) -> usize {
+        self.overflow
+    }
+}
+
+/// A sorted copy of the values without the ones that don't compare with themselves (NaN).
+fn sorted<T: Copy + PartialOrd>(values: Iter<'_, T>) -> Vec<T> {
+    let mut sorted: Vec<T> = values.filter(|value| value.partial_cmp(value).is_some()).copied().collect();
+    sorted.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    sorted
+}
+
+/// The nearest-rank `q` quantile of sorted values: the value at rank `ceil(q * len)`, counting from 1,
+/// and the smallest value for `q = 0`. None for no values or a `q` outside of [0, 1].
+fn nearest_rank<T: Copy>(sorted: &[T], q: f64) -> Option<T> {
+    if !(0.0..=1.0).contains(&q) || sorted.is_empty() {
+        return None;
+    }
+    let position = q * sorted.len() as f64;
+    let mut rank = position as usize;
+    if (rank as f64) < position {
+        rank += 1;
+    }
+    Some(sorted[rank.max(1) - 1])
+}
+
+/// Statistics over the live elements of any rolling buffer, computed by walking `iter()`,
+/// so they never see the unused or freed slots of an underfilled buffer. Only the quantiles
+/// allocate, for a sorted copy of the window; the buffer itself is never reordered.
+/// Implemented for everything that implements `RollingRead`.
+///
+/// ```
+/// # use rolling_buffer::{buffer::{buffer::RollingBuffer, stats::RollingStats}, prelude::*};
+/// let mut latencies = RollingBuffer::new(4);
+/// latencies.extend([12.0, 15.0, 11.0, 14.0, 18.0]);
+/// assert_eq!(latencies.sum(), 58.0);
+/// assert_eq!(latencies.mean(), Some(14.5));
+/// ```
+pub trait RollingStats<T>: RollingRead<T> {
+    /// Sum of the live elements in a wider accumulator, see `Sample::Sum`. 0 for an empty buffer.
+    fn sum(&self) -> T::Sum
+    where
+        T: Sample,
+    {
+        T::sum(self.iter())
+    }
+
+    /// Arithmetic mean of the live elements, None for an empty buffer.
+    fn mean(&self) -> Option<f64>
+    where
+        T: Sample,
+    {
+        if self.is_empty() {
+            return None;
+        }
+        Some(T::sum_to_f64(self.sum()) / self.len() as f64)
+    }
+
+    /// Population variance of the live elements, the mean squared deviation from `mean()` (dividing by `len()`),
+    /// so a single element has a variance of 0. None for an empty buffer.
+    /// Computed in two passes with the rounding error of the mean corrected, which stays accurate for
+    /// large values with a small spread where summing the squares would cancel out.
+    fn variance(&self) -> Option<f64>
+    where
+        T: Sample,
+    {
+        let mean = self.mean()?;
+        Some(variance_around(self.iter(), mean))
+    }
+
+    /// Root mean square of the live elements, `sqrt(mean(x²))`. Only live elements count, not the unused
+    /// or freed slots of an underfilled buffer. None for an empty buffer. Needs the `std` feature for `f64::sqrt`.
+    #[cfg(feature = "std")]
+    fn rms(&self) -> Option<f64>
+    where
+        T: Sample,
+    {
+        if self.is_empty() {
+            return None;
+        }
+        let squares = compensated_sum(self.iter().map(|value| value.to_f64() * value.to_f64()));
+        Some((squares / self.len() as f64).sqrt())
+    }
+
+    /// Number of consecutive pairs of live elements, in logical order, for which `pred` changes its result.
+    /// `pred` is called once per element.
+    fn transitions(&self, mut pred: impl FnMut(&T) -> bool) -> usize {
+        let mut values = self.iter().map(&mut pred);
+        let Some(mut previous) = values.next() else {
+            return 0;
+        };
+        values.filter(|&current| current != mem::replace(&mut previous, current)).count()
+    }
+
+    /// Number of sign changes between the live elements in logical order. Exact zeros (and NaN) don't have
+    /// a sign and are skipped, so `[1, 0, -1]` crosses zero once and `[1, 0, 1]` doesn't cross it at all.
+    fn zero_crossings(&self) -> usize
+    where
+        T: Sample,
+    {
+        let mut signs = self.iter().map(|value| value.to_f64()).filter(|&value| value != 0.0 && !value.is_nan());
+        let Some(mut previous) = signs.next().map(|value| value > 0.0) else {
+            return 0;
+        };
+        signs.map(|value| value > 0.0).filter(|&current| current != mem::replace(&mut previous, current)).count()
+    }
+
+    /// Standard score of the newest element: how many standard deviations it lies above (positive)
+    /// or below (negative) the mean, `(last - mean) / std_dev`. With `exclude_newest` the mean and standard
+    /// deviation are taken over the window without the newest element, so an outlier doesn't pull them
+    /// towards itself; otherwise over the whole window, as `mean()` and `std_dev()`.
+    /// None if fewer than two elements are left to compare against or their standard deviation is 0.
+    /// Needs the `std` feature for `f64::sqrt`.
+    ///
+    /// ```
+    /// # use rolling_buffer::{buffer::{buffer::RollingBuffer, stats::RollingStats}, prelude::*};
+    /// let mut readings = RollingBuffer::new(5);
+    /// readings.extend([20.0, 21.0, 19.0, 20.0, 26.0]);
+    /// assert_eq!(readings.zscore_last(true), Some(6.0 / 0.5f64.sqrt()));
+    /// assert!((readings.zscore_last(false).unwrap() - 4.8 / 6.16f64.sqrt()).abs() < 1e-12);
+    /// ```
+    #[cfg(feature = "std")]
+    fn zscore_last(&self, exclude_newest: bool) -> Option<f64>
+    where
+        T: Sample,
+    {
+        let last = self.last()?.to_f64();
+        let len = if exclude_newest { self.len() - 1 } else { self.len() };
+        if len < 2 {
+            return None;
+        }
+        let window = self.iter().take(len);
+        let mean = compensated_sum(window.clone().map(|value| value.to_f64())) / len as f64;
+        let std_dev = variance_around(window, mean).sqrt();
+        if std_dev == 0.0 {
+            return None;
+        }
+        Some((last - mean) / std_dev)
+    }
+
+    /// Mean of the live elements weighted by `weights`, where `weights[0]` applies to the oldest element
+    /// and the last weight to the newest: `sum(weights[i] * element i) / sum(weights)`.
+    /// None for an empty buffer, if `weights.len()` is not `len()` or if the weights sum to 0.
+    fn weighted_mean(&self, weights: &[f64]) -> Option<f64>
+    where
+        T: Sample,
+    {
+        if self.is_empty() || weights.len() != self.len() {
+            return None;
+        }
+        let total = compensated_sum(weights.iter().copied());
+        if total == 0.0 {
+            return None;
+        }
+        let weighted = compensated_sum(self.iter().zip(weights).map(|(value, weight)| value.to_f64() * weight));
+        Some(weighted / total)
+    }
+
+    /// Linearly weighted moving average: the oldest live element has a weight of 1, the next one 2,
+    /// up to `len()` for the newest. None for an empty buffer.
+    fn linear_weighted_mean(&self) -> Option<f64>
+    where
+        T: Sample,
+    {
+        if self.is_empty() {
+            return None;
+        }
+        let len = self.len() as f64;
+        let weighted = compensated_sum(self.iter().enumerate().map(|(k, value)| (k + 1) as f64 * value.to_f64()));
+        Some(weighted / (len * (len + 1.0) / 2.0))
+    }
+
+    /// Square root of `variance()`. Needs the `std` feature for `f64::sqrt`.
+    #[cfg(feature = "std")]
+    fn std_dev(&self) -> Option<f64>
+    where
+        T: Sample,
+    {
+        self.variance().map(f64::sqrt)
+    }
+
+    /// Smallest live element with its logical index, the oldest one if several are equal.
+    /// Elements that don't compare with themselves (NaN) are skipped, so the result is None
+    /// only for a buffer without comparable elements.
+    ///
+    /// Not called `min()`, since for `T: Ord` the buffer itself is `Ord` and `buffer.min()` resolves to `Ord::min`.
+    fn min_indexed(&self) -> Option<Indexed<'_, T>>
+    where
+        T: PartialOrd,
+    {
+        extreme(self.iter(), self.count().wrapping_sub(self.len()), Ordering::Less)
+    }
+
+    /// Largest live element with its logical index, the oldest one if several are equal.
+    /// NaN is skipped as in `min_indexed()`.
+    fn max_indexed(&self) -> Option<Indexed<'_, T>>
+    where
+        T: PartialOrd,
+    {
+        extreme(self.iter(), self.count().wrapping_sub(self.len()), Ordering::Greater)
+    }
+
+    /// The `k` largest live elements, largest first, without sorting the window. `k` is clamped to `len()`.
+    /// Equal elements are returned oldest first, and if they don't all make it into the top `k`,
+    /// the oldest ones are kept.
+    ///
+    /// ```
+    /// # use rolling_buffer::{buffer::{buffer::RollingBuffer, stats::RollingStats}, prelude::*};
+    /// let mut durations = RollingBuffer::new(5);
+    /// durations.extend([90, 12, 40, 7, 55, 40]);
+    /// assert_eq!(durations.top_k(3), [&55, &40, &40]);
+    /// ```
+    fn top_k(&self, k: usize) -> Vec<&T>
+    where
+        T: Ord,
+    {
+        top(self.iter(), k, |value| value)
+    }
+
+    /// Same as `top_k()`, ranking the elements by the key `f` returns for them, which is computed once per element.
+    /// Elements with equal keys are returned oldest first, and the oldest ones are kept.
+    fn top_k_by_key<K, F>(&self, k: usize, f: F) -> Vec<&T>
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        top(self.iter(), k, f)
+    }
+
+    /// `(min_indexed(), max_indexed())` in a single pass.
+    fn minmax_indexed(&self) -> Option<(Indexed<'_, T>, Indexed<'_, T>)>
+    where
+        T: PartialOrd,
+    {
+        let start = self.count().wrapping_sub(self.len());
+        let mut extremes: Option<(Indexed<'_, T>, Indexed<'_, T>)> = None;
+        for (k, value) in self.iter().enumerate() {
+            if value.partial_cmp(value).is_none() {
+                continue;
+            }
+            let entry = (start.wrapping_add(k), value);
+            match &mut extremes {
+                None => extremes = Some((entry, entry)),
+                Some((min, max)) => {
+                    if value < min.1 {
+                        *min = entry;
+                    } else if value > max.1 {
+                        *max = entry;
+                    }
+                }
+            }
+        }
+        extremes
+    }
+
+    /// Histogram of the live elements in `bins` bins of equal width between the smallest and the largest one,
+    /// so there is no underflow or overflow. A window of equal elements gets the range of width 1
+    /// centered on its value, or a few ulps wide for values so large that ±0.5 would round back to them.
+    /// A single pass over the live elements finds the range while copying them as f64, the copy is then
+    /// counted into the bins. None for `bins = 0` or a buffer without comparable elements.
+    fn histogram(&self, bins: usize) -> Option<Histogram>
+    where
+        T: Sample,
+    {
+        if bins == 0 {
+            return None;
+        }
+        let mut values = Vec::with_capacity(self.len());
+        let (mut low, mut high) = (f64::INFINITY, f64::NEG_INFINITY);
+        for value in self.iter().map(|value| value.to_f64()).filter(|value| !value.is_nan()) {
+            (low, high) = (low.min(value), high.max(value));
+            values.push(value);
+        }
+        if values.is_empty() {
+            return None;
+        }
+        if low == high {
+            let step = f64::max(0.5, low.abs() * f64::EPSILON);
+            (low, high) = (low - step, high + step);
+        }
+        let mut edges: Vec<f64> = (0..bins).map(|i| low + (high - low) * i as f64 / bins as f64).collect();
+        edges.push(high);
+        Some(Histogram::count(values.into_iter(), edges))
+    }
+
+    /// Histogram of the live elements in the bins between the given `edges`, counting the elements
+    /// outside of them as underflow and overflow, in a single pass.
+    /// None if there are fewer than two edges or they aren't strictly increasing.
+    fn histogram_with_edges(&self, edges: &[f64]) -> Option<Histogram>
+    where
+        T: Sample,
+    {
+        if edges.len() < 2 || !edges.windows(2).all(|pair| pair[0] < pair[1]) {
+            return None;
+        }
+        Some(Histogram::count(self.iter().map(|value| value.to_f64()), edges.to_vec()))
+    }
+
+    /// The `q` quantile of the live elements by nearest rank: the smallest element that at least
+    /// a `q` share of the elements is less than or equal to, so `quantile(0.5)` is the lower median,
+    /// `quantile(0.0)` the minimum and `quantile(1.0)` the maximum. It is always one of the elements,
+    /// never an interpolation between two. NaN is skipped as in `min_indexed()`.
+    /// None for a `q` outside of [0, 1] (or NaN) and for a buffer without comparable elements.
+    ///
+    /// Sorts a copy of the window, use `quantiles()` to get several quantiles out of a single sort.
+    fn quantile(&self, q: f64) -> Option<T>
+    where
+        T: Copy + PartialOrd,
+    {
+        if !(0.0..=1.0).contains(&q) {
+            return None;
+        }
+        nearest_rank(&sorted(self.iter()), q)
+    }
+
+    /// `quantile()` for each of `qs`, in the same order, sorting the copy of the window only once.
+    /// None if any of `qs` is outside of [0, 1] or the buffer holds no comparable elements.
+    ///
+    /// ```
+    /// # use rolling_buffer::{buffer::{buffer::RollingBuffer, stats::RollingStats}, prelude::*};
+    /// let mut durations = RollingBuffer::new(100);
+    /// durations.extend(1..=120u32);
+    /// assert_eq!(durations.quantiles(&[0.5, 0.95, 0.99]), Some(vec![70, 115, 119]));
+    /// ```
+    fn quantiles(&self, qs: &[f64]) -> Option<Vec<T>>
+    where
+        T: Copy + PartialOrd,
+    {
+        if !qs.iter().all(|q| (0.0..=1.0).contains(q)) {
+            return None;
+        }
+        let sorted = sorted(self.iter());
+        qs.iter().map(|&q| nearest_rank(&sorted, q)).collect()
+    }
+}
+
+impl<T, R> RollingStats<T> for R where R: RollingRead<T> + ?Sized {}
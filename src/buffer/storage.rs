@@ -0,0 +1,229 @@
+use alloc::vec::Vec;
+
+use super::iter::{Iter, IterMut};
+use super::ring;
+use super::traits::{RollingRead, RollingWrite};
+
+/// Fixed size slots for a RollingStorage, implemented by `[T; N]` for RollingArray and by
+/// `heapless::Vec<T, N>` for RollingHeapless. The ring logic lives in RollingStorage,
+/// a Storage only decides how a slot is filled for the first time and how it is emptied.
+pub trait Storage<T>: AsRef<[T]> + AsMut<[T]> {
+    /// Number of slots, which is the size of the rolling buffer.
+    const SIZE: usize;
+
+    /// Name of the rolling buffer type, used in panic messages.
+    const NAME: &'static str;
+
+    /// Creates the slots of an empty buffer.
+    fn empty() -> Self;
+
+    /// Stores `value` at `index`, the first slot without a live element of a buffer that isn't full yet.
+    fn store(&mut self, index: usize, value: T);
+
+    /// Drops every element, leaving the slots as `empty()` created them.
+    fn reset(&mut self);
+}
+
+/// RollingStorage is a fixed size rolling buffer over `S::SIZE` slots that live wherever the buffer itself
+/// is placed, so it never touches the heap. It has the same logical indices, `count` and `last_removed`
+/// as a RollingBuffer of the same size. Use it through the RollingArray and RollingHeapless aliases.
+///
+/// ['slots']: slots stores the elements, the k-th live element is stored at (head + k) % S::SIZE
+/// ['last_removed']: last_removed is the last element that was evicted by a push
+/// ['count']: count is the number of elements in the buffer as if the buffer was Vec, logical indices are count truncated to usize
+/// ['len']: len is the number of live elements
+/// ['head']: head is the index of the oldest live element
+#[derive(Debug, Clone)]
+pub struct RollingStorage<T, S> {
+    slots: S,
+    last_removed: Option<T>,
+    count: u64,
+    len: usize,
+    head: usize,
+}
+
+impl<T, S: Storage<T>> Default for RollingStorage<T, S> {
+    /// Creates an empty buffer over `S::empty()`.
+    fn default() -> Self {
+        Self {
+            slots: S::empty(),
+            last_removed: None,
+            count: 0,
+            len: 0,
+            head: 0,
+        }
+    }
+}
+
+impl<T, S: Storage<T>> RollingStorage<T, S> {
+    /// Returns the slots as they are stored, same as `RollingBuffer::raw()`.
+    pub fn raw(&self) -> &S {
+        &self.slots
+    }
+
+    /// Index of the k-th live element, k may be at most `S::SIZE`.
+    fn slot(&self, k: usize) -> usize {
+        ring::slot(self.head, k, S::SIZE)
+    }
+
+    /// Maps a logical index to a slot, None if it was evicted or not pushed yet.
+    fn position(&self, i: usize) -> Option<usize> {
+        ring::window_offset(self.count, self.len, i).ok().map(|k| self.slot(k))
+    }
+}
+
+impl<T, S: Storage<T>> RollingRead<T> for RollingStorage<T, S> {
+    /// Get the element at the given logical index, None for indices that were already evicted or not pushed yet.
+    fn get(&self, i: usize) -> Option<&T> {
+        let index = self.position(i)?;
+        Some(&self.slots.as_ref()[index])
+    }
+
+    /// Returns the element `n` steps back from the newest one, `get_back(0)` is the newest element.
+    fn get_back(&self, n: usize) -> Option<&T> {
+        if n >= self.len {
+            return None;
+        }
+        Some(&self.slots.as_ref()[self.slot(self.len - 1 - n)])
+    }
+
+    /// Returns the most recently pushed live element.
+    fn last(&self) -> Option<&T> {
+        self.get_back(0)
+    }
+
+    /// Returns the oldest live element.
+    fn first(&self) -> Option<&T> {
+        (self.len > 0).then(|| &self.slots.as_ref()[self.head])
+    }
+
+    /// Returns the number of live elements.
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `S::SIZE`, the maximum number of elements that can be stored.
+    fn size(&self) -> usize {
+        S::SIZE
+    }
+
+    /// Returns the last element evicted by a push.
+    fn last_removed(&self) -> &Option<T> {
+        &self.last_removed
+    }
+
+    /// Returns the number of pushed elements as if the buffer was a Vec, truncated to usize.
+    fn count(&self) -> usize {
+        self.count as usize
+    }
+
+    /// Same as `count()`, as a 64-bit number that doesn't wrap on 32-bit targets.
+    fn total_pushed(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns true if there are no live elements.
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns true if the next push evicts the oldest element.
+    fn is_full(&self) -> bool {
+        self.len == S::SIZE
+    }
+
+    /// Number of pushes left before the buffer is full, never None since the size is fixed.
+    fn remaining_capacity(&self) -> Option<usize> {
+        Some(S::SIZE - self.len)
+    }
+
+    /// Share of the slots holding live elements, from 0.0 (empty) to 1.0 (full).
+    fn fill_ratio(&self) -> f64 {
+        self.len as f64 / S::SIZE as f64
+    }
+
+    /// Creates a new Vec with the live elements, oldest first. `iter()` reads them without allocating.
+    fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+
+    /// Returns an iterator over references to the live elements, oldest first.
+    fn iter(&self) -> Iter<'_, T> {
+        let (first, second) = self.as_slices();
+        Iter::new(first, second)
+    }
+
+    /// Returns the live elements as at most two runs, oldest first.
+    fn as_slices(&self) -> (&[T], &[T]) {
+        ring::as_slices(self.slots.as_ref(), self.head, self.len, S::SIZE)
+    }
+}
+
+impl<T, S: Storage<T>> RollingWrite<T> for RollingStorage<T, S> {
+    /// Same as `default()`, the size is fixed by the type and must be equal to `S::SIZE`.
+    ///
+    /// # Panics
+    /// Panics if `size` is not `S::SIZE`.
+    fn new(size: usize) -> Self {
+        assert_eq!(size, S::SIZE, "the size of a {} is fixed to {}", S::NAME, S::SIZE);
+        Self::default()
+    }
+
+    /// Adds an element to the buffer, evicting the oldest one into `last_removed` when it is full.
+    fn push(&mut self, value: T) {
+        if self.len == S::SIZE {
+            let index = self.head;
+            self.last_removed = Some(core::mem::replace(&mut self.slots.as_mut()[index], value));
+            self.head = self.slot(1);
+        } else {
+            // an underfilled buffer never wraps, so the next slot is always the first one without a live element
+            debug_assert_eq!(self.slot(self.len), self.len);
+            self.slots.store(self.len, value);
+            self.len += 1;
+        }
+        self.count = self.count.wrapping_add(1);
+    }
+
+    /// Mutable version of `get()`.
+    fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        let index = self.position(i)?;
+        Some(&mut self.slots.as_mut()[index])
+    }
+
+    /// Mutable version of `get_back()`.
+    fn get_back_mut(&mut self, n: usize) -> Option<&mut T> {
+        if n >= self.len {
+            return None;
+        }
+        let index = self.slot(self.len - 1 - n);
+        Some(&mut self.slots.as_mut()[index])
+    }
+
+    /// Most recently pushed element's mutable reference.
+    fn last_mut(&mut self) -> Option<&mut T> {
+        self.get_back_mut(0)
+    }
+
+    /// Oldest live element's mutable reference.
+    fn first_mut(&mut self) -> Option<&mut T> {
+        (self.len > 0).then(|| &mut self.slots.as_mut()[self.head])
+    }
+
+    /// Empties the buffer through `Storage::reset()`. `count` goes back to 0 and `last_removed` to `None`.
+    fn clear(&mut self) {
+        self.slots.reset();
+        self.count = 0;
+        self.len = 0;
+        self.head = 0;
+        self.last_removed = None;
+    }
+
+    /// Returns an iterator over mutable references to the live elements, oldest first.
+    fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (first, second) = ring::as_mut_slices(self.slots.as_mut(), self.head, self.len, S::SIZE);
+        IterMut::new(first, second)
+    }
+}
@@ -1,16 +1,14 @@
-pub trait Rolling<T> 
-where
-    T: Clone + Default,
-{
-    fn new(size: usize) -> Self;
+use alloc::vec::Vec;
 
-    fn push(&mut self, value: T);
+use super::iter::{Iter, IterMut};
 
+/// Read-only access to a rolling buffer.
+pub trait RollingRead<T> {
     fn get(&self, i: usize) -> Option<&T>;
 
-    fn last(&self) -> Option<&T>;
+    fn get_back(&self, n: usize) -> Option<&T>;
 
-    fn last_mut(&mut self) -> Option<&mut T>;
+    fn last(&self) -> Option<&T>;
 
     fn first(&self) -> Option<&T>;
 
@@ -18,13 +16,53 @@ where
 
     fn size(&self) -> usize;
 
-    fn raw(&self) -> &Vec<T>;
-
     fn last_removed(&self) -> &Option<T>;
 
     fn count(&self) -> usize;
 
+    fn total_pushed(&self) -> u64;
+
     fn is_empty(&self) -> bool;
-    
-    fn to_vec(&self) -> Vec<T>;
+
+    fn is_full(&self) -> bool;
+
+    fn remaining_capacity(&self) -> Option<usize>;
+
+    fn fill_ratio(&self) -> f64;
+
+    fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone;
+
+    fn iter(&self) -> Iter<'_, T>;
+
+    /// The live elements as at most two contiguous slices, oldest first, for callers that want slice access
+    /// without depending on how a buffer type stores its elements.
+    fn as_slices(&self) -> (&[T], &[T]);
 }
+
+/// Construction and mutation of a rolling buffer.
+pub trait RollingWrite<T> {
+    fn new(size: usize) -> Self
+    where
+        Self: Sized;
+
+    fn push(&mut self, value: T);
+
+    fn get_mut(&mut self, i: usize) -> Option<&mut T>;
+
+    fn get_back_mut(&mut self, n: usize) -> Option<&mut T>;
+
+    fn last_mut(&mut self) -> Option<&mut T>;
+
+    fn first_mut(&mut self) -> Option<&mut T>;
+
+    fn clear(&mut self);
+
+    fn iter_mut(&mut self) -> IterMut<'_, T>;
+}
+
+/// Full read and write access, implemented for everything that implements both halves.
+pub trait Rolling<T>: RollingRead<T> + RollingWrite<T> {}
+
+impl<T, R> Rolling<T> for R where R: RollingRead<T> + RollingWrite<T> {}
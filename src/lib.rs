@@ -1,8 +1,28 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+
+extern crate alloc;
+
 pub mod buffer;
 
+/// Re-exports the rolling buffer traits, so a single glob import brings every trait method into scope.
+///
+/// ```
+/// use rolling_buffer::{buffer::buffer::RollingBuffer, prelude::*};
+///
+/// let mut buffer = RollingBuffer::<i32>::new(2);
+/// buffer.extend([1, 2, 3]);
+/// assert_eq!(buffer.to_vec(), [2, 3]);
+/// ```
+pub mod prelude {
+    pub use crate::buffer::traits::{Rolling, RollingRead, RollingWrite};
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::buffer::{buffer::RollingBuffer, traits::Rolling};
+    use std::rc::Rc;
+
+    use crate::buffer::{array::RollingArray, buffer::RollingBuffer, deque::RollingDeque, error::{BuildError, GetError, RawPartsError}, ext::RollingIteratorExt, traits::{Rolling, RollingRead, RollingWrite}};
     
     #[test]
     fn test_rolling_data_underflow() {
@@ -10,13 +30,13 @@ mod tests {
         data.push(1);
         data.push(2);
 
-        assert_eq!(*data.raw(), [1, 2, 0, 0]);
+        assert_eq!(*data.raw(), [1, 2]);
         assert_eq!(*data.to_vec(), [1, 2]);
         assert_eq!(*data.last().unwrap_or(&0), 2);
         assert_eq!(*data.first().unwrap_or(&0), 1);
         assert_eq!(data.size(), 4);
         assert_eq!(data.count(), 2);
-        assert_eq!(data.last_removed().unwrap(), 0);
+        assert_eq!(*data.last_removed(), None);
     }
     
     #[test]
@@ -68,4 +88,3347 @@ mod tests {
         assert_eq!(data.size(), 0);
         assert_eq!(data.to_vec(), [1, 2, 3, 4, 5]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_iter() {
+        let mut data = RollingBuffer::<i32>::new(4);
+        assert_eq!(data.iter().count(), 0);
+        data.push(1);
+        data.push(2);
+        assert_eq!(data.iter().copied().collect::<Vec<_>>(), [1, 2]);
+        data.push(3);
+        data.push(4);
+        assert_eq!(data.iter().copied().collect::<Vec<_>>(), [1, 2, 3, 4]);
+        data.push(5);
+        data.push(6);
+        assert_eq!(*data.raw(), [5, 6, 3, 4]);
+        assert_eq!(data.iter().copied().collect::<Vec<_>>(), data.to_vec());
+
+        let mut data = RollingBuffer::<i32>::new(0);
+        data.push(1);
+        data.push(2);
+        data.push(3);
+        assert_eq!(data.iter().copied().collect::<Vec<_>>(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_iter_mut() {
+        let mut data = RollingBuffer::<i32>::new(4);
+        data.push(1);
+        data.push(2);
+        data.iter_mut().for_each(|x| *x *= 10);
+        assert_eq!(*data.raw(), [10, 20]);
+
+        data.push(3);
+        data.push(4);
+        data.push(5);
+        let mut order = Vec::new();
+        for x in data.iter_mut() {
+            order.push(*x);
+            *x += 1;
+        }
+        assert_eq!(order, [20, 3, 4, 5]);
+        assert_eq!(data.to_vec(), [21, 4, 5, 6]);
+
+        let mut data = RollingBuffer::<i32>::new(0);
+        data.push(1);
+        data.push(2);
+        data.iter_mut().for_each(|x| *x = -*x);
+        assert_eq!(data.to_vec(), [-1, -2]);
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let mut data = RollingBuffer::<String>::new(3);
+        data.push("a".to_string());
+        data.push("b".to_string());
+        assert_eq!(data.clone().into_iter().collect::<Vec<_>>(), ["a", "b"]);
+        data.push("c".to_string());
+        data.push("d".to_string());
+        let mut collected = Vec::new();
+        for x in data {
+            collected.push(x);
+        }
+        assert_eq!(collected, ["b", "c", "d"]);
+
+        let mut data = RollingBuffer::<i32>::new(0);
+        data.push(1);
+        data.push(2);
+        assert_eq!(data.into_iter().collect::<Vec<_>>(), [1, 2]);
+    }
+
+    #[test]
+    fn test_into_iter_drops_remaining() {
+        let value = Rc::new(0);
+        let mut data = RollingBuffer::<Rc<i32>>::new(4);
+        for _ in 0..6 {
+            data.push(value.clone());
+        }
+        assert_eq!(Rc::strong_count(&value), 6);
+        let mut iter = data.into_iter();
+        let first = iter.next().unwrap();
+        drop(iter);
+        assert_eq!(Rc::strong_count(&value), 2);
+        drop(first);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn test_into_iter_ref() {
+        fn sum<'a>(it: impl IntoIterator<Item = &'a i32>) -> i32 {
+            it.into_iter().sum()
+        }
+
+        let mut data = RollingBuffer::<i32>::new(3);
+        for i in 1..=5 {
+            data.push(i);
+        }
+        assert_eq!(sum(&data), 12);
+        let mut seen = Vec::new();
+        for x in &data {
+            seen.push(*x);
+        }
+        assert_eq!(seen, [3, 4, 5]);
+        for x in &mut data {
+            *x *= 2;
+        }
+        assert_eq!((&data).into_iter().collect::<Vec<_>>(), [&6, &8, &10]);
+    }
+
+    #[test]
+    fn test_iter_rev() {
+        let mut data = RollingBuffer::<i32>::new(4);
+        for i in 1..=11 {
+            data.push(i);
+        }
+        assert_eq!(*data.raw(), [9, 10, 11, 8]);
+        assert_eq!(data.iter().rev().copied().collect::<Vec<_>>(), [11, 10, 9, 8]);
+        assert_eq!(data.clone().into_iter().rev().collect::<Vec<_>>(), [11, 10, 9, 8]);
+
+        let mut iter = data.iter();
+        assert_eq!(iter.next(), Some(&8));
+        assert_eq!(iter.next_back(), Some(&11));
+        assert_eq!(iter.next_back(), Some(&10));
+        assert_eq!(iter.next(), Some(&9));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        let mut iter = data.iter_mut();
+        *iter.next_back().unwrap() = 0;
+        assert_eq!(data.to_vec(), [8, 9, 10, 0]);
+
+        let mut data = RollingBuffer::<i32>::new(4);
+        data.push(1);
+        data.push(2);
+        assert_eq!(data.iter().rev().copied().collect::<Vec<_>>(), [2, 1]);
+    }
+
+    #[test]
+    fn test_iter_exact_size() {
+        let mut data = RollingBuffer::<i32>::new(4);
+        data.push(1);
+        data.push(2);
+        assert_eq!(data.iter().len(), 2);
+        for i in 3..=9 {
+            data.push(i);
+        }
+        let mut iter = data.iter();
+        assert_eq!(iter.size_hint(), (4, Some(4)));
+        iter.next();
+        iter.next_back();
+        assert_eq!(iter.len(), 2);
+        assert_eq!(data.iter_mut().len(), 4);
+        let mut iter = data.clone().into_iter();
+        iter.next();
+        assert_eq!(iter.len(), 3);
+
+        let mut iter = data.iter();
+        while iter.next().is_some() {}
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.len(), 0);
+    }
+
+    #[test]
+    fn test_iter_indexed() {
+        let mut data = RollingBuffer::<i32>::new(4);
+        data.push(10);
+        data.push(11);
+        assert_eq!(data.iter_indexed().collect::<Vec<_>>(), [(0, &10), (1, &11)]);
+        for i in 12..=15 {
+            data.push(i);
+        }
+        assert_eq!(
+            data.iter_indexed().collect::<Vec<_>>(),
+            [(2, &12), (3, &13), (4, &14), (5, &15)]
+        );
+        assert_eq!(data.iter_indexed().next_back(), Some((5, &15)));
+        for (i, value) in data.iter_indexed() {
+            assert_eq!(data.get(i), Some(value));
+        }
+    }
+
+    #[test]
+    fn test_windows() {
+        let mut data = RollingBuffer::<i32>::new(5);
+        data.push(1);
+        data.push(2);
+        assert_eq!(data.windows(3).count(), 0);
+        for i in 3..=8 {
+            data.push(i);
+        }
+        assert_eq!(*data.raw(), [6, 7, 8, 4, 5]);
+        let windows = data
+            .windows(3)
+            .map(|w| w.copied().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(windows, [[4, 5, 6], [5, 6, 7], [6, 7, 8]]);
+        assert_eq!(data.windows(3).len(), 3);
+        assert_eq!(data.windows(5).next_back().unwrap().sum::<i32>(), 30);
+        assert_eq!(data.windows(1).map(|w| w.len()).collect::<Vec<_>>(), [1; 5]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_windows_zero() {
+        let data = RollingBuffer::<i32>::new(5);
+        data.windows(0);
+    }
+
+    #[test]
+    fn test_chunks() {
+        let mut data = RollingBuffer::<i32>::new(7);
+        for i in 1..=10 {
+            data.push(i);
+        }
+        assert_eq!(*data.raw(), [8, 9, 10, 4, 5, 6, 7]);
+        let chunks = data
+            .chunks(3)
+            .map(|c| c.copied().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(chunks, [vec![4, 5, 6], vec![7, 8, 9], vec![10]]);
+        assert_eq!(data.chunks(3).len(), 3);
+        let chunks = data
+            .chunks(3)
+            .rev()
+            .map(|c| c.copied().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(chunks, [vec![10], vec![7, 8, 9], vec![4, 5, 6]]);
+
+        let exact = data.chunks_exact(2);
+        assert_eq!(exact.remainder().copied().collect::<Vec<_>>(), [10]);
+        let chunks = exact
+            .map(|c| c.copied().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(chunks, [[4, 5], [6, 7], [8, 9]]);
+        assert_eq!(data.chunks_exact(7).len(), 1);
+        assert_eq!(RollingBuffer::<i32>::new(3).chunks(2).count(), 0);
+    }
+
+    #[test]
+    fn test_deltas() {
+        let mut data = RollingBuffer::<i32>::new(4);
+        assert_eq!(data.deltas().count(), 0);
+        data.push(5);
+        assert_eq!(data.deltas().count(), 0);
+        data.push(7);
+        assert_eq!(data.deltas().collect::<Vec<_>>(), [2]);
+
+        let mut total = 0;
+        for i in 1..=13 {
+            total += i;
+            data.push(total);
+        }
+        assert_eq!(*data.raw(), [66, 78, 91, 55]);
+        assert_eq!(data.deltas().collect::<Vec<_>>(), [11, 12, 13]);
+        assert_eq!(data.deltas().len(), 3);
+        assert_eq!(data.map_pairs(|a, b| a < b).collect::<Vec<_>>(), [true; 3]);
+    }
+
+    #[test]
+    fn test_last_n() {
+        let mut data = RollingBuffer::<i32>::new(5);
+        data.push(1);
+        data.push(2);
+        assert_eq!(data.last_n(3).copied().collect::<Vec<_>>(), [1, 2]);
+        for i in 3..=9 {
+            data.push(i);
+        }
+        assert_eq!(*data.raw(), [6, 7, 8, 9, 5]);
+        assert_eq!(data.last_n(2).copied().collect::<Vec<_>>(), [8, 9]);
+        assert_eq!(data.last_n(3).copied().collect::<Vec<_>>(), [7, 8, 9]);
+        assert_eq!(data.last_n(5).copied().collect::<Vec<_>>(), [5, 6, 7, 8, 9]);
+        assert_eq!(data.last_n(100).len(), 5);
+        assert_eq!(data.last_n(0).len(), 0);
+
+        let mut data = RollingBuffer::<i32>::new(0);
+        for i in 1..=4 {
+            data.push(i);
+        }
+        assert_eq!(data.last_n(10).copied().collect::<Vec<_>>(), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_first_n() {
+        let mut data = RollingBuffer::<i32>::new(4);
+        data.push(1);
+        data.push(2);
+        assert_eq!(data.first_n(1).copied().collect::<Vec<_>>(), [1]);
+        assert_eq!(data.first_n(3).copied().collect::<Vec<_>>(), [1, 2]);
+        data.push(3);
+        data.push(4);
+        assert_eq!(data.first_n(3).copied().collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(data.first_n(4).copied().collect::<Vec<_>>(), [1, 2, 3, 4]);
+        for i in 5..=14 {
+            data.push(i);
+        }
+        assert_eq!(*data.raw(), [13, 14, 11, 12]);
+        assert_eq!(data.first_n(3).copied().collect::<Vec<_>>(), [11, 12, 13]);
+        assert_eq!(data.first_n(10).copied().collect::<Vec<_>>(), [11, 12, 13, 14]);
+        assert_eq!(data.first_n(0).len(), 0);
+    }
+
+    #[test]
+    fn test_iter_from() {
+        let mut data = RollingBuffer::<i32>::new(4);
+        data.push(0);
+        data.push(1);
+        let mark = data.count();
+        data.push(2);
+        let iter = data.iter_from(mark);
+        assert_eq!((iter.start_index(), iter.skipped()), (2, 0));
+        assert_eq!(iter.copied().collect::<Vec<_>>(), [2]);
+
+        for i in 3..=9 {
+            data.push(i);
+        }
+        let iter = data.iter_from(mark);
+        assert_eq!((iter.start_index(), iter.skipped()), (6, 4));
+        assert_eq!(iter.copied().collect::<Vec<_>>(), [6, 7, 8, 9]);
+        assert_eq!(data.iter_from(8).copied().collect::<Vec<_>>(), [8, 9]);
+        let iter = data.iter_from(data.count());
+        assert_eq!((iter.start_index(), iter.len()), (10, 0));
+        assert_eq!(data.iter_from(100).len(), 0);
+    }
+
+    #[test]
+    fn test_drain() {
+        let mut data = RollingBuffer::<i32>::new(4);
+        for i in 1..=6 {
+            data.push(i);
+        }
+        assert_eq!(data.drain().collect::<Vec<_>>(), [3, 4, 5, 6]);
+        assert!(data.is_empty());
+        assert_eq!(data.len(), 0);
+        assert_eq!(*data.last_removed(), None);
+        assert_eq!((data.raw().len(), data.capacity()), (0, 4));
+        data.push(7);
+        assert_eq!(data.to_vec(), [7]);
+
+        let value = Rc::new(0);
+        let mut data = RollingBuffer::<Rc<i32>>::new(3);
+        for _ in 0..5 {
+            data.push(value.clone());
+        }
+        let first = data.drain().next().unwrap();
+        assert_eq!(Rc::strong_count(&value), 2);
+        drop(first);
+
+        let mut data = RollingBuffer::<i32>::new(0);
+        for i in 1..=100 {
+            data.push(i);
+        }
+        let capacity = data.capacity();
+        assert_eq!(data.drain().rev().take(2).collect::<Vec<_>>(), [100, 99]);
+        assert!(data.is_empty());
+        assert_eq!(data.capacity(), capacity);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_iter() {
+        use rayon::prelude::*;
+
+        let mut data = RollingBuffer::<f64>::new(1000);
+        for i in 0..700 {
+            data.push(i as f64);
+        }
+        assert_eq!(data.par_iter().sum::<f64>(), data.iter().sum::<f64>());
+        for i in 700..2500 {
+            data.push(i as f64);
+        }
+        assert_eq!(data.par_iter().sum::<f64>(), data.iter().sum::<f64>());
+        let positions = data
+            .par_iter()
+            .enumerate()
+            .map(|(i, x)| (i, *x))
+            .collect::<Vec<_>>();
+        assert!(positions.iter().all(|(i, x)| *x == (1500 + i) as f64));
+        data.par_iter_mut().for_each(|x| *x *= 2.0);
+        assert_eq!(data.first(), Some(&3000.0));
+        assert_eq!((&data).into_par_iter().count(), 1000);
+    }
+
+    #[test]
+    fn test_from_iter() {
+        let data = (1..=5).collect::<RollingBuffer<i32>>();
+        assert_eq!(data.size(), 0);
+        assert_eq!(data.count(), 5);
+        assert_eq!(data.len(), 5);
+        assert_eq!(data.to_vec(), [1, 2, 3, 4, 5]);
+        assert_eq!(data.last(), Some(&5));
+
+        let mut data = "abc".chars().collect::<RollingBuffer<char>>();
+        data.push('d');
+        assert_eq!(data.to_vec(), ['a', 'b', 'c', 'd']);
+        assert_eq!(data.count(), 4);
+        assert!(std::iter::empty::<i32>().collect::<RollingBuffer<_>>().is_empty());
+    }
+
+    #[test]
+    fn test_from_vec() {
+        let mut data = RollingBuffer::from_vec(4, vec![1, 2]);
+        assert_eq!(*data.raw(), [1, 2]);
+        assert_eq!(data.to_vec(), [1, 2]);
+        assert_eq!((data.count(), data.len()), (2, 2));
+        assert_eq!(*data.last_removed(), None);
+        data.push(3);
+        assert_eq!(data.to_vec(), [1, 2, 3]);
+
+        let mut data = RollingBuffer::from_vec(4, vec![1, 2, 3, 4]);
+        assert_eq!(*data.raw(), [1, 2, 3, 4]);
+        assert_eq!(*data.last_removed(), None);
+        data.push(5);
+        assert_eq!(data.to_vec(), [2, 3, 4, 5]);
+        assert_eq!(*data.last_removed(), Some(1));
+
+        let mut reference = RollingBuffer::<i32>::new(4);
+        for i in 1..=7 {
+            reference.push(i);
+        }
+        let mut data = RollingBuffer::from_vec(4, (1..=7).collect());
+        assert_eq!(*data.raw(), *reference.raw());
+        assert_eq!(data.to_vec(), [4, 5, 6, 7]);
+        assert_eq!(data.count(), 7);
+        assert_eq!(*data.last_removed(), Some(3));
+        data.push(8);
+        reference.push(8);
+        assert_eq!(*data.raw(), *reference.raw());
+
+        let data = RollingBuffer::from(vec![1, 2, 3]);
+        assert_eq!((data.size(), data.count()), (0, 3));
+        assert_eq!(RollingBuffer::from_vec(0, vec![1, 2, 3]).to_vec(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_from_slice() {
+        let samples = [0.5f32, 1.5, 2.5, 3.5, 4.5];
+        let mut data = RollingBuffer::from_slice(3, &samples);
+        assert_eq!(data.to_vec(), [2.5, 3.5, 4.5]);
+        assert_eq!(data.count(), 5);
+        assert_eq!(*data.last_removed(), Some(1.5));
+        data.push(5.5);
+        assert_eq!(data.to_vec(), [3.5, 4.5, 5.5]);
+        assert_eq!(*data.raw(), *RollingBuffer::from_vec(3, vec![0.5, 1.5, 2.5, 3.5, 4.5, 5.5]).raw());
+
+        let mut data = RollingBuffer::from_slice(8, &samples[..2]);
+        assert_eq!((data.len(), data.count()), (2, 2));
+        data.push(9.0);
+        assert_eq!(data.to_vec(), [0.5, 1.5, 9.0]);
+        assert_eq!(RollingBuffer::from_slice(0, &samples).to_vec(), samples);
+    }
+
+    #[test]
+    fn test_extend() {
+        for len in [0, 2, 4, 5, 6, 23] {
+            let mut reference = RollingBuffer::<i32>::new(4);
+            reference.push(-1);
+            let mut data = reference.clone();
+            for i in 0..len {
+                reference.push(i);
+            }
+            data.extend(0..len);
+            assert_eq!(*data.raw(), *reference.raw());
+            assert_eq!(data.count(), reference.count());
+            assert_eq!(data.last_removed(), reference.last_removed());
+
+            // Without an exact size hint every item goes through push.
+            let mut data = RollingBuffer::<i32>::new(4);
+            data.push(-1);
+            data.extend((0..len).filter(|_| true));
+            assert_eq!(*data.raw(), *reference.raw());
+            assert_eq!(data.last_removed(), reference.last_removed());
+        }
+
+        let mut data = RollingBuffer::<i32>::new(3);
+        data.extend(&[1, 2, 3, 4]);
+        assert_eq!(data.to_vec(), [2, 3, 4]);
+        assert_eq!(*data.last_removed(), Some(1));
+
+        let mut data = RollingBuffer::<i32>::new(0);
+        data.extend([1, 2, 3]);
+        assert_eq!((data.to_vec(), data.count()), (vec![1, 2, 3], 3));
+    }
+
+    #[test]
+    fn test_extend_from_slice() {
+        let values = (0..30).collect::<Vec<i32>>();
+        for prefill in 0..6 {
+            for len in 0..values.len() {
+                let mut reference = RollingBuffer::<i32>::new(5);
+                for i in 0..prefill {
+                    reference.push(-i);
+                }
+                let mut data = reference.clone();
+                for value in &values[..len] {
+                    reference.push(*value);
+                }
+                data.extend_from_slice(&values[..len]);
+                assert_eq!(*data.raw(), *reference.raw());
+                assert_eq!(data.count(), reference.count());
+                assert_eq!(data.last_removed(), reference.last_removed());
+            }
+        }
+
+        let mut data = RollingBuffer::<String>::new(0);
+        data.extend_from_slice(&["a".to_string(), "b".to_string()]);
+        assert_eq!((data.to_vec(), data.count()), (vec!["a".to_string(), "b".to_string()], 2));
+    }
+
+    #[test]
+    fn test_into_vec() {
+        let mut data = RollingBuffer::<String>::new(4);
+        data.push("a".to_string());
+        data.push("b".to_string());
+        assert_eq!(data.clone().into_vec(), ["a", "b"]);
+        for value in ["c", "d", "e", "f", "g"] {
+            data.push(value.to_string());
+        }
+        let expected = data.to_vec();
+        let pointer = data.raw().as_ptr();
+        let vec = data.into_vec();
+        assert_eq!(vec, expected);
+        assert_eq!(vec, ["d", "e", "f", "g"]);
+        assert_eq!(vec.as_ptr(), pointer);
+
+        let data = RollingBuffer::from(vec![1, 2, 3]);
+        assert_eq!(data.into_vec(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_to_vec_into() {
+        let mut out = vec![-1; 16];
+        let capacity = out.capacity();
+        let mut data = RollingBuffer::<i32>::new(4);
+        data.to_vec_into(&mut out);
+        assert!(out.is_empty());
+        for i in 1..=10 {
+            data.push(i);
+            data.to_vec_into(&mut out);
+            assert_eq!(out, data.to_vec());
+        }
+        assert_eq!(out.capacity(), capacity);
+
+        let data = RollingBuffer::from(vec![1, 2, 3]);
+        data.to_vec_into(&mut out);
+        assert_eq!(out, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_vec_deque() {
+        use std::collections::VecDeque;
+
+        let mut data = RollingBuffer::<i32>::new(4);
+        data.push(1);
+        data.push(2);
+        let deque = VecDeque::from(data.clone());
+        assert_eq!(deque, [1, 2]);
+        let restored = RollingBuffer::from_deque(4, deque);
+        assert_eq!(restored.to_vec(), data.to_vec());
+        assert_eq!(*restored.raw(), *data.raw());
+
+        for i in 3..=9 {
+            data.push(i);
+        }
+        let deque = VecDeque::from(data.clone());
+        assert_eq!(deque, [6, 7, 8, 9]);
+        let restored = RollingBuffer::from_deque(4, deque);
+        assert_eq!(restored.to_vec(), data.to_vec());
+
+        let restored = RollingBuffer::from_deque(2, VecDeque::from(vec![1, 2, 3, 4, 5]));
+        assert_eq!(restored.to_vec(), [4, 5]);
+        assert_eq!(restored.count(), 5);
+        assert_eq!(*restored.last_removed(), Some(3));
+    }
+
+    #[test]
+    fn test_new_filled() {
+        let mut data = RollingBuffer::new_filled(4, 0.0);
+        assert_eq!(data.len(), 4);
+        assert_eq!(data.count(), 4);
+        assert_eq!(data.to_vec(), [0.0; 4]);
+        assert_eq!(data.first(), Some(&0.0));
+        assert_eq!(data.last(), Some(&0.0));
+        assert_eq!(data.get(3), Some(&0.0));
+        data.push(1.0);
+        assert_eq!(data.to_vec(), [0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(*data.last_removed(), Some(0.0));
+        assert_eq!(data.count(), 5);
+
+        let data = RollingBuffer::new_filled(0, 1);
+        assert!(data.is_empty());
+    }
+
+    #[test]
+    fn test_builder() {
+        let mut data = RollingBuffer::<i32>::builder().size(4).build().unwrap();
+        data.push(1);
+        assert_eq!(*data.raw(), [1]);
+
+        let mut data = RollingBuffer::builder()
+            .size(3)
+            .prefill(1..=5)
+            .build()
+            .unwrap();
+        assert_eq!(data.to_vec(), [3, 4, 5]);
+        assert_eq!((data.count(), *data.last_removed()), (5, Some(2)));
+        data.push(6);
+        assert_eq!(*data.last_removed(), Some(3));
+
+        let mut data = RollingBuffer::builder()
+            .size(2)
+            .prefill(1..=3)
+            .track_removed(false)
+            .build()
+            .unwrap();
+        assert_eq!(*data.last_removed(), None);
+        data.push(4);
+        assert_eq!(data.to_vec(), [3, 4]);
+        assert_eq!(*data.last_removed(), None);
+
+        let error = RollingBuffer::builder().size(2).prefill(1..=3).strict(true).build();
+        assert_eq!(error.unwrap_err(), BuildError::PrefillTooLong { size: 2, len: 3 });
+
+        let data = RollingBuffer::<i32>::builder().unbounded().prefill([1, 2]).build().unwrap();
+        assert_eq!((data.size(), data.to_vec()), (0, vec![1, 2]));
+    }
+
+    #[test]
+    fn test_builder_without_default() {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Reading(u32);
+
+        let data = RollingBuffer::builder()
+            .size(2)
+            .prefill([Reading(1), Reading(2), Reading(3)])
+            .build_full()
+            .unwrap();
+        assert_eq!(data.into_vec(), [Reading(2), Reading(3)]);
+        let error = RollingBuffer::builder()
+            .size(4)
+            .prefill([Reading(1)])
+            .build_full()
+            .unwrap_err();
+        assert_eq!(error, BuildError::PrefillTooShort { size: 4, len: 1 });
+        assert_eq!(error.to_string(), "prefill of 1 values does not cover a buffer of size 4");
+    }
+
+    #[test]
+    fn test_new_with() {
+        let mut data = RollingBuffer::new_with(4, |i| i as f32 / 4.0);
+        assert_eq!(data.to_vec(), [0.0, 0.25, 0.5, 0.75]);
+        assert_eq!((data.len(), data.count()), (4, 4));
+        data.push(1.0);
+        assert_eq!(*data.last_removed(), Some(0.0));
+        assert_eq!(data.to_vec(), [0.25, 0.5, 0.75, 1.0]);
+
+        data.push(2.0);
+        data.fill_with(|i| i as f32);
+        assert_eq!(data.to_vec(), [0.0, 1.0, 2.0, 3.0]);
+        assert_eq!(*data.last_removed(), None);
+        data.push(4.0);
+        assert_eq!(*data.last_removed(), Some(0.0));
+
+        let mut data = RollingBuffer::from(vec![5, 6]);
+        data.fill_with(|i| i * 10);
+        assert_eq!(data.to_vec(), [0, 10]);
+    }
+
+    #[test]
+    fn test_raw_parts() {
+        let mut data = RollingBuffer::<i32>::new(4);
+        for i in 1..=6 {
+            data.push(i);
+        }
+        let (vec, size, count, last_removed) = data.clone().into_raw_parts();
+        assert_eq!((vec.clone(), size, count, last_removed), (vec![5, 6, 3, 4], 4, 6, Some(2)));
+        let mut restored = RollingBuffer::from_raw_parts(vec, size, count, last_removed).unwrap();
+        assert_eq!(restored.to_vec(), data.to_vec());
+        restored.push(7);
+        data.push(7);
+        assert_eq!(*restored.raw(), *data.raw());
+
+        assert_eq!(
+            RollingBuffer::from_raw_parts(vec![1, 2, 3], 2, 3, None).unwrap_err(),
+            RawPartsError::LengthMismatch { size: 2, len: 3 }
+        );
+        assert_eq!(
+            RollingBuffer::from_raw_parts(vec![1, 2], 4, 1, None).unwrap_err(),
+            RawPartsError::CountMismatch { count: 1, len: 2 }
+        );
+        assert_eq!(
+            RollingBuffer::from_raw_parts(vec![1, 2], 0, 1, None).unwrap_err(),
+            RawPartsError::CountMismatch { count: 1, len: 2 }
+        );
+        let data = RollingBuffer::from_raw_parts(vec![1, 2], 0, 2, None).unwrap();
+        assert_eq!(data.to_vec(), [1, 2]);
+    }
+
+    #[test]
+    fn test_raw_parts_after_pop() {
+        let mut data = RollingBuffer::<i32>::new(3);
+        data.extend([1, 2, 3, 4]);
+        data.pop();
+        let (vec, size, count, last_removed) = data.clone().into_raw_parts();
+        assert_eq!((vec.clone(), size, count, last_removed), (vec![2, 3], 3, 3, Some(1)));
+        let restored = RollingBuffer::from_raw_parts(vec, size, count, last_removed).unwrap();
+        assert_eq!((restored.to_vec(), restored.live_range()), (vec![2, 3], 1..3));
+
+        let mut data = RollingBuffer::<i32>::new(3);
+        data.extend([1, 2, 3]);
+        data.pop_oldest();
+        let (vec, size, count, last_removed) = data.clone().into_raw_parts();
+        assert_eq!((vec.clone(), count), (vec![2, 3], 3));
+        let mut restored = RollingBuffer::from_raw_parts(vec, size, count, last_removed).unwrap();
+        assert_eq!((restored.to_vec(), restored.live_range()), (vec![2, 3], 1..3));
+        restored.push(4);
+        data.push(4);
+        assert_eq!(restored.to_vec(), data.to_vec());
+    }
+
+    #[test]
+    fn test_as_slices() {
+        let mut data = RollingBuffer::<i32>::new(4);
+        assert_eq!(data.as_slices(), (&[][..], &[][..]));
+        data.push(1);
+        data.push(2);
+        assert_eq!(data.as_slices(), (&[1, 2][..], &[][..]));
+        data.push(3);
+        data.push(4);
+        assert_eq!(data.as_slices(), (&[1, 2, 3, 4][..], &[][..]));
+        data.push(5);
+        assert_eq!(data.as_slices(), (&[2, 3, 4][..], &[5][..]));
+        data.push(6);
+        data.push(7);
+        data.push(8);
+        assert_eq!(data.as_slices(), (&[5, 6, 7, 8][..], &[][..]));
+
+        data.push(9);
+        let (first, second) = data.as_mut_slices();
+        first[0] = 0;
+        second[0] = 90;
+        assert_eq!(data.to_vec(), [0, 7, 8, 90]);
+
+        let data = RollingBuffer::from(vec![1, 2, 3]);
+        assert_eq!(data.as_slices(), (&[1, 2, 3][..], &[][..]));
+    }
+
+    #[test]
+    fn test_make_contiguous() {
+        let mut data = RollingBuffer::<i32>::new(4);
+        data.push(1);
+        data.push(2);
+        assert_eq!(data.make_contiguous(), [1, 2]);
+        assert_eq!(*data.raw(), [1, 2]);
+
+        for i in 3..=7 {
+            data.push(i);
+        }
+        assert_eq!(*data.raw(), [5, 6, 7, 4]);
+        let removed = *data.last_removed();
+        assert_eq!(data.make_contiguous(), [4, 5, 6, 7]);
+        assert_eq!(*data.raw(), [4, 5, 6, 7]);
+        assert_eq!(data.as_slices(), (&[4, 5, 6, 7][..], &[][..]));
+        assert_eq!((data.count(), data.len(), *data.last_removed()), (7, 4, removed));
+        assert_eq!(data.get(3), Some(&4));
+        assert_eq!(data.get(6), Some(&7));
+        assert_eq!((data.first(), data.last()), (Some(&4), Some(&7)));
+
+        data.make_contiguous()[0] = 40;
+        data.push(8);
+        assert_eq!(*data.last_removed(), Some(40));
+        assert_eq!(data.to_vec(), [5, 6, 7, 8]);
+        assert_eq!(data.get(7), Some(&8));
+
+        let (vec, size, count, _) = data.clone().into_raw_parts();
+        let mut reference = RollingBuffer::<i32>::new(4);
+        for i in 1..=8 {
+            reference.push(i);
+        }
+        assert_eq!((vec, size, count), (reference.raw().to_vec(), 4, 8));
+
+        let mut data = RollingBuffer::from(vec![1, 2, 3]);
+        assert_eq!(data.make_contiguous(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_collect_rolling() {
+        let data = ["1", "2", "x", "4", "5"]
+            .iter()
+            .filter_map(|line| line.parse::<i32>().ok())
+            .collect_rolling(3);
+        assert_eq!(data.to_vec(), [2, 4, 5]);
+        assert_eq!(data.count(), 4);
+        assert_eq!(*data.last_removed(), Some(1));
+
+        let data = (0..2).collect_rolling(3);
+        assert_eq!((data.to_vec(), data.count()), (vec![0, 1], 2));
+        let data = (0..5).collect_rolling(0);
+        assert_eq!(data.to_vec(), [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_get_mut() {
+        let mut data = RollingBuffer::<i32>::new(4);
+        assert_eq!(data.get_mut(0), None);
+        data.push(1);
+        data.push(2);
+        *data.get_mut(1).unwrap() += 10;
+        assert_eq!(data.to_vec(), [1, 12]);
+        assert_eq!(data.get_mut(2), None);
+
+        for i in 3..=6 {
+            data.push(i);
+        }
+        assert_eq!(data.get_mut(0), None);
+        assert_eq!(data.get_mut(1), None);
+        *data.get_mut(2).unwrap() = 30;
+        *data.get_mut(5).unwrap() = 60;
+        assert_eq!(data.to_vec(), [30, 4, 5, 60]);
+        assert_eq!(data.get_mut(6), None);
+
+        let mut data = RollingBuffer::from(vec![1, 2, 3]);
+        *data.get_mut(2).unwrap() = 0;
+        assert_eq!(data.to_vec(), [1, 2, 0]);
+        assert_eq!(data.get_mut(3), None);
+    }
+
+    #[test]
+    fn test_get_live_window() {
+        let mut data = RollingBuffer::<i32>::new(4);
+        data.push(1);
+        data.push(2);
+        assert_eq!((data.get(0), data.get(1)), (Some(&1), Some(&2)));
+        assert_eq!(data.get(2), None);
+        assert_eq!(data.get(3), None);
+
+        data.push(3);
+        data.push(4);
+        assert_eq!((data.get(0), data.get(3)), (Some(&1), Some(&4)));
+        assert_eq!(data.get(4), None);
+
+        data.push(5);
+        data.push(6);
+        assert_eq!(data.get(0), None);
+        assert_eq!(data.get(1), None);
+        assert_eq!((data.get(2), data.get(5)), (Some(&3), Some(&6)));
+        assert_eq!(data.get(6), None);
+        assert_eq!(data.get(100), None);
+
+        let data = RollingBuffer::from(vec![1, 2, 3]);
+        assert_eq!(data.get(2), Some(&3));
+        assert_eq!(data.get(3), None);
+        assert_eq!(RollingBuffer::<i32>::new(0).get(0), None);
+    }
+
+    #[test]
+    fn test_index() {
+        let mut data = RollingBuffer::<i32>::new(4);
+        for i in 1..=9 {
+            data.push(i);
+        }
+        assert_eq!(data[5], 6);
+        assert_eq!(data[8], 9);
+        assert_eq!((5..9).map(|i| data[i]).collect::<Vec<_>>(), data.to_vec());
+    }
+
+    #[test]
+    #[should_panic(expected = "logical index 3 evicted; live range is 5..9")]
+    fn test_index_evicted() {
+        let mut data = RollingBuffer::<i32>::new(4);
+        for i in 1..=9 {
+            data.push(i);
+        }
+        let _ = data[3];
+    }
+
+    #[test]
+    #[should_panic(expected = "logical index 9 not yet pushed; live range is 5..9")]
+    fn test_index_not_yet_pushed() {
+        let mut data = RollingBuffer::<i32>::new(4);
+        for i in 1..=9 {
+            data.push(i);
+        }
+        let _ = data[9];
+    }
+
+    #[test]
+    fn test_index_mut() {
+        let mut data = RollingBuffer::<i32>::new(3);
+        for i in 1..=5 {
+            data.push(i);
+        }
+        data[2] += 10;
+        data[4] *= 2;
+        assert_eq!(data.to_vec(), [13, 4, 10]);
+    }
+
+    #[test]
+    #[should_panic(expected = "logical index 1 evicted; live range is 2..5")]
+    fn test_index_mut_evicted() {
+        let mut data = RollingBuffer::<i32>::new(3);
+        for i in 1..=5 {
+            data.push(i);
+        }
+        data[1] = 0;
+    }
+
+    #[test]
+    fn test_logical_to_physical() {
+        let mut data = RollingBuffer::<i32>::new(4);
+        assert_eq!(data.logical_to_physical(0), None);
+        data.push(1);
+        data.push(2);
+        assert_eq!(data.logical_to_physical(0), Some(0));
+        assert_eq!(data.logical_to_physical(1), Some(1));
+        assert_eq!(data.logical_to_physical(2), None);
+
+        for i in 3..=7 {
+            data.push(i);
+        }
+        let mapped = (0..9).map(|i| data.logical_to_physical(i)).collect::<Vec<_>>();
+        assert_eq!(mapped, [None, None, None, Some(3), Some(0), Some(1), Some(2), None, None]);
+
+        data.make_contiguous();
+        let mapped = (3..7).map(|i| data.logical_to_physical(i)).collect::<Vec<_>>();
+        assert_eq!(mapped, [Some(0), Some(1), Some(2), Some(3)]);
+
+        let data = RollingBuffer::from(vec![1, 2, 3]);
+        assert_eq!(data.logical_to_physical(2), Some(2));
+        assert_eq!(data.logical_to_physical(3), None);
+    }
+
+    #[test]
+    fn test_first_mut() {
+        let mut data = RollingBuffer::<i32>::new(3);
+        assert_eq!(data.first_mut(), None);
+        data.push(1);
+        data.push(2);
+        *data.first_mut().unwrap() = 10;
+        assert_eq!(data.to_vec(), [10, 2]);
+
+        for i in 3..=10 {
+            data.push(i);
+            *data.first_mut().unwrap() *= -1;
+            assert_eq!(data.first_mut().map(|x| *x), data.first().copied());
+        }
+        assert_eq!(*data.raw(), [10, -8, 9]);
+        assert_eq!(data.to_vec(), [-8, 9, 10]);
+
+        let mut data = RollingBuffer::from(vec![1, 2]);
+        *data.first_mut().unwrap() = 0;
+        assert_eq!(data.to_vec(), [0, 2]);
+    }
+
+    #[test]
+    fn test_get_back() {
+        let mut data = RollingBuffer::<i32>::new(4);
+        assert_eq!(data.get_back(0), None);
+        data.push(1);
+        data.push(2);
+        assert_eq!((data.get_back(0), data.get_back(1)), (Some(&2), Some(&1)));
+        assert_eq!(data.get_back(2), None);
+
+        for i in 3..=9 {
+            data.push(i);
+        }
+        assert_eq!(data.get_back(0), Some(&9));
+        assert_eq!(data.get_back(3), Some(&6));
+        assert_eq!(data.get_back(4), None);
+        assert_eq!(data.get_back(usize::MAX), None);
+        *data.get_back_mut(1).unwrap() = 0;
+        assert_eq!(data.to_vec(), [6, 7, 0, 9]);
+
+        let data = RollingBuffer::from(vec![1, 2, 3]);
+        assert_eq!((data.get_back(0), data.get_back(2)), (Some(&3), Some(&1)));
+        assert_eq!(data.get_back(3), None);
+    }
+
+    #[test]
+    fn test_get_checked() {
+        let mut data = RollingBuffer::<i32>::new(4);
+        assert_eq!(data.get_checked(0), Err(GetError::NotYetPushed { index: 0, next: 0 }));
+        for i in 1..=9 {
+            data.push(i);
+        }
+        assert_eq!(data.get_checked(5), Ok(&6));
+        assert_eq!(data.get_checked(4), Err(GetError::Evicted { index: 4, oldest_live: 5 }));
+        assert_eq!(data.get_checked(9), Err(GetError::NotYetPushed { index: 9, next: 9 }));
+        for i in 0..12 {
+            assert_eq!(data.get_checked(i).ok(), data.get(i));
+        }
+        let error = data.get_checked(1).unwrap_err();
+        assert_eq!(error.to_string(), "logical index 1 evicted; oldest live index is 5");
+        let error: Box<dyn std::error::Error> = Box::new(error);
+        assert!(error.source().is_none());
+    }
+
+    #[test]
+    fn test_range() {
+        let mut data = RollingBuffer::<i32>::new(5);
+        for i in 0..13 {
+            data.push(i * 10);
+        }
+        assert_eq!(data.range(9..11).copied().collect::<Vec<_>>(), [90, 100]);
+        assert_eq!(data.range(10..=12).copied().collect::<Vec<_>>(), [100, 110, 120]);
+        let range = data.range(2..10);
+        assert_eq!(range.start_index(), 8);
+        assert_eq!(range.copied().collect::<Vec<_>>(), [80, 90]);
+        let range = data.range(11..);
+        assert_eq!(range.start_index(), 11);
+        assert_eq!(range.len(), 2);
+        assert_eq!(data.range(..).copied().collect::<Vec<_>>(), data.to_vec());
+        assert_eq!(data.range(20..30).len(), 0);
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = data.range(11..9);
+        assert_eq!(reversed.len(), 0);
+        assert_eq!(data.range(0..3).len(), 0);
+    }
+
+    #[test]
+    fn test_live_range() {
+        let mut data = RollingBuffer::<i32>::new(3);
+        assert_eq!((data.oldest_index(), data.live_range()), (None, 0..0));
+        data.push(1);
+        assert_eq!((data.oldest_index(), data.live_range()), (Some(0), 0..1));
+        for i in 2..=7 {
+            data.push(i);
+        }
+        assert_eq!((data.oldest_index(), data.live_range()), (Some(4), 4..7));
+        let data = RollingBuffer::from(vec![1, 2]);
+        assert_eq!((data.oldest_index(), data.live_range()), (Some(0), 0..2));
+    }
+
+    #[test]
+    fn test_live_range_property() {
+        for size in 0..6 {
+            let mut data = RollingBuffer::<usize>::new(size);
+            for pushed in 0..20 {
+                let live = data.live_range();
+                assert_eq!(live.len(), data.len());
+                assert_eq!(live.end, data.count());
+                assert_eq!(data.oldest_index().is_some(), !data.is_empty());
+                for i in 0..live.end + 2 {
+                    assert_eq!(data.get(i).is_some(), live.contains(&i));
+                }
+                assert_eq!(data.iter_indexed().map(|(i, _)| i).collect::<Vec<_>>(), live.collect::<Vec<_>>());
+                data.push(pushed);
+            }
+        }
+    }
+
+    #[test]
+    fn test_push_after_pop_keeps_last_removed() {
+        let mut data = RollingBuffer::<i32>::new(3);
+        for i in 1..=5 {
+            data.push(i);
+        }
+        assert_eq!(*data.last_removed(), Some(2));
+        assert_eq!(data.pop(), Some(5));
+        data.push(6);
+        assert_eq!(*data.last_removed(), Some(2));
+        assert_eq!(data.to_vec(), [3, 4, 6]);
+        data.push(7);
+        assert_eq!(*data.last_removed(), Some(3));
+    }
+
+    #[test]
+    fn test_pop() {
+        let mut data = RollingBuffer::<i32>::new(4);
+        assert_eq!(data.pop(), None);
+        data.push(1);
+        data.push(2);
+        assert_eq!(data.pop(), Some(2));
+        assert_eq!((data.to_vec(), data.count()), (vec![1], 1));
+        assert_eq!(*data.raw(), [1]);
+
+        for i in 2..=6 {
+            data.push(i);
+        }
+        let removed = *data.last_removed();
+        assert_eq!(data.to_vec(), [3, 4, 5, 6]);
+        assert_eq!(data.pop(), Some(6));
+        assert_eq!(data.to_vec(), [3, 4, 5]);
+        assert_eq!((data.len(), data.count()), (3, 5));
+        assert_eq!(data.last(), Some(&5));
+        assert_eq!(data.get(5), None);
+        assert_eq!(data.live_range(), 2..5);
+        assert_eq!(*data.last_removed(), removed);
+
+        data.push(7);
+        assert_eq!(data.to_vec(), [3, 4, 5, 7]);
+        data.push(8);
+        assert_eq!(data.to_vec(), [4, 5, 7, 8]);
+        assert_eq!(*data.last_removed(), Some(3));
+
+        while data.pop().is_some() {}
+        assert!(data.is_empty());
+        assert_eq!((data.first(), data.last()), (None, None));
+        assert_eq!(data.count(), 3);
+
+        let mut data = RollingBuffer::from(vec![1, 2]);
+        assert_eq!(data.pop(), Some(2));
+        assert_eq!((data.to_vec(), data.count()), (vec![1], 1));
+    }
+
+    #[test]
+    fn test_push_after_pop_oldest_keeps_last_removed() {
+        let mut data = RollingBuffer::<i32>::new(3);
+        for i in 1..=5 {
+            data.push(i);
+        }
+        assert_eq!(data.pop_oldest(), Some(3));
+        data.push(6);
+        assert_eq!(*data.last_removed(), Some(2));
+        assert_eq!(data.to_vec(), [4, 5, 6]);
+        data.push(7);
+        assert_eq!(*data.last_removed(), Some(4));
+
+        assert_eq!((data.pop_oldest(), data.pop_oldest()), (Some(5), Some(6)));
+        data.push(8);
+        data.push(9);
+        assert_eq!(*data.last_removed(), Some(4));
+        assert_eq!(data.to_vec(), [7, 8, 9]);
+    }
+
+    #[test]
+    fn test_pop_oldest() {
+        use std::collections::VecDeque;
+
+        let mut data = RollingBuffer::<i32>::new(4);
+        assert_eq!(data.pop_oldest(), None);
+        for i in 1..=6 {
+            data.push(i);
+        }
+        assert_eq!(data.pop_oldest(), Some(3));
+        assert_eq!(data.to_vec(), [4, 5, 6]);
+        assert_eq!((data.len(), data.count()), (3, 6));
+        assert_eq!((data.first(), data.get(2), data.get(3)), (Some(&4), None, Some(&4)));
+        assert_eq!(data.live_range(), 3..6);
+
+        let mut data = RollingBuffer::<i32>::new(5);
+        let mut model = VecDeque::new();
+        for step in 0..200 {
+            if step % 3 == 2 || step % 7 == 0 {
+                assert_eq!(data.pop_oldest(), model.pop_front());
+            } else {
+                data.push(step);
+                model.push_back(step);
+                if model.len() > 5 {
+                    model.pop_front();
+                }
+            }
+            assert_eq!(data.to_vec(), Vec::from(model.clone()));
+            assert_eq!(data.first(), model.front());
+            assert_eq!(data.last(), model.back());
+            for (i, value) in data.iter_indexed() {
+                assert_eq!(data.get(i), Some(value));
+            }
+        }
+
+        let mut data = RollingBuffer::from(vec![1, 2, 3]);
+        assert_eq!(data.pop_oldest(), Some(1));
+        assert_eq!((data.to_vec(), data.live_range()), (vec![2, 3], 1..3));
+        assert_eq!(data.get(1), Some(&2));
+    }
+
+    #[test]
+    fn test_clear() {
+        let value = Rc::new(7);
+        let mut data = RollingBuffer::<Option<Rc<i32>>>::new(3);
+        for _ in 0..5 {
+            data.push(Some(value.clone()));
+        }
+        data.clear();
+        assert_eq!(Rc::strong_count(&value), 1);
+        assert!(data.is_empty());
+        assert!(data.to_vec().is_empty());
+        assert_eq!((data.count(), data.len(), data.size()), (0, 0, 3));
+        assert_eq!(data.last_removed(), &None);
+        assert!(data.raw().iter().all(Option::is_none));
+
+        // logical indices restart at 0
+        data.push(Some(Rc::new(1)));
+        assert_eq!(data.live_range(), 0..1);
+        assert_eq!(data.get(0), Some(&Some(Rc::new(1))));
+
+        let mut data = RollingBuffer::from(vec![1, 2, 3]);
+        let capacity = data.capacity();
+        data.clear();
+        assert!(data.is_empty());
+        assert_eq!((data.count(), data.capacity()), (0, capacity));
+    }
+
+    #[test]
+    fn test_keep_last_and_truncate_back() {
+        let mut data = RollingBuffer::<i32>::new(5);
+        for i in 1..=8 {
+            data.push(i);
+        }
+        data.keep_last(10);
+        assert_eq!(data.to_vec(), [4, 5, 6, 7, 8]);
+        data.keep_last(2);
+        assert_eq!(data.to_vec(), [7, 8]);
+        assert_eq!((data.len(), data.count(), data.size()), (2, 8, 5));
+        assert_eq!((data.get(5), data.get(6)), (None, Some(&7)));
+        assert_eq!(data.iter().copied().collect::<Vec<_>>(), [7, 8]);
+        data.push(9);
+        assert_eq!(data.to_vec(), [7, 8, 9]);
+
+        data.truncate_back(1);
+        assert_eq!(data.to_vec(), [7]);
+        assert_eq!(data.count(), 7);
+        data.truncate_back(0);
+        assert!(data.is_empty());
+
+        let value = Rc::new(0);
+        let mut data = RollingBuffer::<Option<Rc<i32>>>::new(4);
+        for _ in 0..4 {
+            data.push(Some(value.clone()));
+        }
+        data.keep_last(1);
+        assert_eq!(Rc::strong_count(&value), 2);
+
+        let mut data = RollingBuffer::from(vec![1, 2, 3, 4]);
+        data.keep_last(2);
+        assert_eq!((data.to_vec(), data.live_range()), (vec![3, 4], 2..4));
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut data = RollingBuffer::<i32>::new(6);
+        for i in 1..=10 {
+            data.push(i);
+        }
+        assert_eq!(data.to_vec(), [5, 6, 7, 8, 9, 10]);
+        let mut seen = Vec::new();
+        data.retain(|&x| {
+            seen.push(x);
+            x % 2 == 0
+        });
+        assert_eq!(seen, [5, 6, 7, 8, 9, 10]);
+        assert_eq!(data.to_vec(), [6, 8, 10]);
+        assert_eq!((data.len(), data.count()), (3, 10));
+        assert_eq!(data.live_range(), 7..10);
+        assert_eq!((data.get(7), data.get(9)), (Some(&6), Some(&10)));
+        assert_eq!(data.first(), Some(&6));
+        data.push(11);
+        assert_eq!(data.to_vec(), [6, 8, 10, 11]);
+
+        // every window position and every keep mask of a wrapped buffer
+        for pushes in 6..12 {
+            for mask in 0..64u32 {
+                let mut data = RollingBuffer::<u32>::new(6);
+                for i in 0..pushes {
+                    data.push(i);
+                }
+                let keep = |x: &u32| mask >> (x % 6) & 1 == 1;
+                let expected: Vec<u32> = data.to_vec().into_iter().filter(keep).collect();
+                data.retain(keep);
+                assert_eq!(data.to_vec(), expected);
+                assert_eq!(data.len(), expected.len());
+                // removed values are reset in place
+                let non_zero = |v: &[u32]| v.iter().filter(|&&x| x != 0).count();
+                assert_eq!(non_zero(data.raw()), non_zero(&expected));
+                let indexed: Vec<_> = data.iter_indexed().map(|(i, &x)| (i, x)).collect();
+                for (i, x) in indexed {
+                    assert_eq!(data[i], x);
+                }
+                data.push(100);
+                assert_eq!(data.last(), Some(&100));
+                assert_eq!(data.len(), (expected.len() + 1).min(6));
+            }
+        }
+
+        let value = Rc::new(0);
+        let mut data = RollingBuffer::<Option<Rc<i32>>>::new(3);
+        data.push(Some(value.clone()));
+        data.push(None);
+        data.retain(Option::is_none);
+        assert_eq!(Rc::strong_count(&value), 1);
+
+        let mut data = RollingBuffer::from(vec![1, 2, 3, 4]);
+        data.retain(|&x| x != 2);
+        assert_eq!((data.to_vec(), data.live_range()), (vec![1, 3, 4], 1..4));
+    }
+
+    #[test]
+    fn test_fill() {
+        let mut data = RollingBuffer::<f64>::new(4);
+        data.push(1.0);
+        data.push(2.0);
+        data.fill(0.5);
+        assert_eq!(data.to_vec(), [0.5, 0.5]);
+        assert_eq!(data.raw(), &[0.5, 0.5]);
+
+        for i in 0..7 {
+            data.push(i as f64);
+        }
+        data.fill(-1.0);
+        assert_eq!(data.to_vec(), [-1.0; 4]);
+        assert_eq!((data.len(), data.count(), data.live_range()), (4, 9, 5..9));
+    }
+
+    #[test]
+    fn test_swap() {
+        let mut data = RollingBuffer::<i32>::new(5);
+        for i in 0..8 {
+            data.push(i);
+        }
+        // 3 and 4 sit before the wrap point, 5..8 after it
+        assert_eq!(data.as_slices(), (&[3, 4][..], &[5, 6, 7][..]));
+        data.swap(3, 7);
+        data.swap(4, 4);
+        assert_eq!(data.to_vec(), [7, 4, 5, 6, 3]);
+        data.swap(5, 6);
+        assert_eq!(data.to_vec(), [7, 4, 6, 5, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "logical index 1 evicted")]
+    fn test_swap_evicted() {
+        let mut data = RollingBuffer::<i32>::new(3);
+        for i in 0..5 {
+            data.push(i);
+        }
+        data.swap(1, 4);
+    }
+
+    #[test]
+    fn test_reverse() {
+        for pushes in 0..10 {
+            let mut data = RollingBuffer::<i32>::new(4);
+            for i in 0..pushes {
+                data.push(i);
+            }
+            let mut expected = data.to_vec();
+            expected.reverse();
+            data.reverse();
+            assert_eq!(data.to_vec(), expected);
+            assert_eq!((data.len(), data.count()), (expected.len(), pushes as usize));
+        }
+
+        let mut data = RollingBuffer::<i32>::new(3);
+        for i in 1..=5 {
+            data.push(i);
+        }
+        data.reverse();
+        assert_eq!(data.to_vec(), [5, 4, 3]);
+        data.push(6);
+        assert_eq!(data.to_vec(), [4, 3, 6]);
+        assert_eq!(data.last_removed(), &Some(5));
+    }
+
+    #[test]
+    fn test_sort() {
+        let mut data = RollingBuffer::<i32>::new(5);
+        for i in [9, 4, 7, 1, 8, 3, 6] {
+            data.push(i);
+        }
+        assert_eq!(data.to_vec(), [7, 1, 8, 3, 6]);
+        data.sort_by(|a, b| a.cmp(b));
+        assert_eq!(data.to_vec(), [1, 3, 6, 7, 8]);
+        assert_eq!((data.len(), data.count()), (5, 7));
+        data.push(2);
+        assert_eq!(data.last_removed(), &Some(1));
+        assert_eq!(data.to_vec(), [3, 6, 7, 8, 2]);
+
+        data.sort_unstable_by(|a, b| b.cmp(a));
+        assert_eq!(data.to_vec(), [8, 7, 6, 3, 2]);
+
+        let mut data = RollingBuffer::<(i32, char)>::new(4);
+        for pair in [(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd'), (0, 'e')] {
+            data.push(pair);
+        }
+        data.sort_by_key(|&(key, _)| key);
+        assert_eq!(data.to_vec(), [(0, 'e'), (1, 'b'), (1, 'd'), (2, 'c')]);
+    }
+
+    #[test]
+    fn test_rotate() {
+        for pushes in 0..9 {
+            for n in 0..7 {
+                let mut data = RollingBuffer::<i32>::new(4);
+                for i in 0..pushes {
+                    data.push(i);
+                }
+                let original = data.to_vec();
+                let mut expected = original.clone();
+                if !expected.is_empty() {
+                    let mid = n % expected.len();
+                    expected.rotate_left(mid);
+                }
+                data.rotate_left(n);
+                assert_eq!(data.to_vec(), expected);
+                data.rotate_right(n);
+                assert_eq!(data.to_vec(), original);
+            }
+        }
+
+        let mut data = RollingBuffer::<i32>::new(4);
+        for i in 1..=4 {
+            data.push(i);
+        }
+        data.rotate_left(1);
+        assert_eq!(data.first(), Some(&2));
+        assert_eq!(data.to_vec(), [2, 3, 4, 1]);
+        data.push(5);
+        assert_eq!(data.last_removed(), &Some(2));
+        assert_eq!(data.to_vec(), [3, 4, 1, 5]);
+
+        let mut data = RollingBuffer::<i32>::new(4);
+        for i in 1..=3 {
+            data.push(i);
+        }
+        data.rotate_right(1);
+        assert_eq!(data.to_vec(), [3, 1, 2]);
+        data.push(4);
+        data.push(5);
+        assert_eq!(data.last_removed(), &Some(3));
+        assert_eq!(data.to_vec(), [1, 2, 4, 5]);
+    }
+
+    #[test]
+    fn test_dedup() {
+        // the run of 2s crosses the wrap point
+        let mut data = RollingBuffer::<i32>::new(6);
+        for i in [9, 9, 1, 1, 2, 2, 2, 3, 1, 1] {
+            data.push(i);
+        }
+        assert_eq!(data.as_slices(), (&[2, 2][..], &[2, 3, 1, 1][..]));
+        data.dedup();
+        assert_eq!(data.to_vec(), [2, 3, 1]);
+        assert_eq!((data.len(), data.count(), data.live_range()), (3, 10, 7..10));
+        data.push(1);
+        assert_eq!(data.to_vec(), [2, 3, 1, 1]);
+
+        for pushes in 0..12 {
+            let mut data = RollingBuffer::<i32>::new(5);
+            let mut values = Vec::new();
+            for i in 0..pushes {
+                data.push(i / 3);
+                values.push(i / 3);
+            }
+            let mut expected = values[values.len().saturating_sub(5)..].to_vec();
+            expected.dedup();
+            data.dedup();
+            assert_eq!(data.to_vec(), expected);
+        }
+
+        let mut data = RollingBuffer::<i32>::new(4);
+        for i in [10, 11, 20, 31, 32] {
+            data.push(i);
+        }
+        data.dedup_by_key(|x| x / 10);
+        assert_eq!(data.to_vec(), [11, 20, 31]);
+
+        let mut data = RollingBuffer::from(vec![1, 1, 2, 2, 1]);
+        data.dedup();
+        assert_eq!((data.to_vec(), data.live_range()), (vec![1, 2, 1], 2..5));
+    }
+
+    #[test]
+    fn test_resize() {
+        // shrinking a wrapped buffer
+        let mut data = RollingBuffer::<i32>::new(5);
+        for i in 0..8 {
+            data.push(i);
+        }
+        assert_eq!(data.as_slices(), (&[3, 4][..], &[5, 6, 7][..]));
+        data.resize(2);
+        assert_eq!(data.to_vec(), [6, 7]);
+        assert_eq!((data.size(), data.len(), data.count()), (2, 2, 8));
+        assert_eq!(data.last_removed(), &Some(5));
+        assert_eq!((data.first(), data.get(6), data.get(5)), (Some(&6), Some(&6), None));
+        data.push(8);
+        assert_eq!((data.to_vec(), data.last_removed()), (vec![7, 8], &Some(6)));
+
+        // shrinking without evicting keeps last_removed
+        let mut data = RollingBuffer::<i32>::new(5);
+        for i in 0..7 {
+            data.push(i);
+        }
+        data.pop_oldest();
+        data.pop_oldest();
+        data.resize(3);
+        assert_eq!((data.to_vec(), data.last_removed()), (vec![4, 5, 6], &Some(1)));
+
+        // growing
+        data.resize(6);
+        assert_eq!((data.to_vec(), data.live_range()), (vec![4, 5, 6], 4..7));
+        for i in 7..10 {
+            data.push(i);
+        }
+        assert_eq!(data.to_vec(), [4, 5, 6, 7, 8, 9]);
+        data.push(10);
+        assert_eq!((data.to_vec(), data.last_removed()), (vec![5, 6, 7, 8, 9, 10], &Some(4)));
+
+        // to and from unbounded
+        data.resize(0);
+        data.push(11);
+        assert_eq!((data.size(), data.to_vec()), (0, vec![5, 6, 7, 8, 9, 10, 11]));
+        assert_eq!(data.get(5), Some(&5));
+        data.resize(3);
+        assert_eq!((data.to_vec(), data.live_range()), (vec![9, 10, 11], 9..12));
+        assert_eq!(data.last_removed(), &Some(8));
+    }
+
+    #[test]
+    fn test_append() {
+        for (own, other) in [(0, 0), (2, 3), (3, 9), (7, 2), (6, 6)] {
+            let mut data = RollingBuffer::<i32>::new(5);
+            let mut more = RollingBuffer::<i32>::new(4);
+            for i in 0..own {
+                data.push(i);
+            }
+            for i in 100..100 + other {
+                more.push(i);
+            }
+            let mut expected = data.to_vec();
+            expected.extend(more.to_vec());
+            let expected = expected[expected.len().saturating_sub(5)..].to_vec();
+            data.append(&mut more);
+            assert_eq!(data.to_vec(), expected);
+            assert_eq!(data.count(), (own + other.min(4)) as usize);
+            assert!(more.is_empty());
+            assert_eq!(more.count(), 0);
+        }
+    }
+
+    #[test]
+    fn test_lazy_allocation() {
+        let mut data = RollingBuffer::<u64>::new(10_000_000);
+        assert_eq!(data.capacity(), 0);
+        data.push(1);
+        data.push(2);
+        assert!(data.capacity() < 1000);
+        assert_eq!((data.get(0), data.first(), data.last()), (Some(&1), Some(&1), Some(&2)));
+
+        let mut data = RollingBuffer::<i32>::new(4);
+        for i in 0..4 {
+            assert_eq!(data.raw().len(), i as usize);
+            data.push(i);
+        }
+        assert_eq!(*data.last_removed(), None);
+        data.push(4);
+        data.push(5);
+        assert_eq!(*data.raw(), [4, 5, 2, 3]);
+        assert_eq!(data.to_vec(), [2, 3, 4, 5]);
+        assert_eq!(*data.last_removed(), Some(1));
+        assert_eq!((data.first(), data.last(), data.get(2), data.get(1)), (Some(&2), Some(&5), Some(&2), None));
+
+        // slots freed before the buffer filled up are reused before growing again
+        let mut data = RollingBuffer::<i32>::new(5);
+        data.extend_from_slice(&[1, 2, 3]);
+        data.pop_oldest();
+        data.retain(|&x| x != 2);
+        assert_eq!((data.to_vec(), data.live_range()), (vec![3], 2..3));
+        let (vec, size, count, last_removed) = data.clone().into_raw_parts();
+        assert_eq!((vec.clone(), size, count), (vec![3], 5, 3));
+        let mut restored = RollingBuffer::from_raw_parts(vec, size, count, last_removed).unwrap();
+        for i in 4..=9 {
+            data.push(i);
+            restored.push(i);
+        }
+        assert_eq!(data.raw().len(), 5);
+        assert_eq!(data.to_vec(), [5, 6, 7, 8, 9]);
+        assert_eq!(restored.to_vec(), data.to_vec());
+        assert_eq!(data.get(4), Some(&5));
+
+        // skipping ahead while growing
+        let mut data = RollingBuffer::<i32>::new(3);
+        data.push(0);
+        data.extend(1..10);
+        assert_eq!((data.to_vec(), *data.last_removed()), (vec![7, 8, 9], Some(6)));
+        data.clear();
+        assert_eq!(data.raw().len(), 0);
+        data.extend_from_slice(&[1, 2, 3, 4, 5]);
+        assert_eq!((data.to_vec(), *data.last_removed()), (vec![3, 4, 5], Some(2)));
+    }
+
+    #[test]
+    fn test_capacity_control() {
+        let mut data = RollingBuffer::<i32>::new(0);
+        data.reserve(100);
+        assert!(data.capacity() >= 100);
+        data.extend(0..1000);
+        assert!(data.capacity() >= 1000);
+        data.clear();
+        data.shrink_to_fit();
+        assert_eq!(data.capacity(), 0);
+        data.push(1);
+        data.shrink_to_fit();
+        assert_eq!((data.capacity(), data.to_vec()), (1, vec![1]));
+
+        let mut data = RollingBuffer::<i32>::new(4);
+        data.reserve(100);
+        assert_eq!(data.capacity(), 0);
+        data.extend(0..10);
+        let capacity = data.capacity();
+        data.shrink_to_fit();
+        assert_eq!(data.capacity(), capacity);
+        assert_eq!(data.to_vec(), [6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_occupancy() {
+        let mut data = RollingBuffer::<i32>::new(4);
+        assert!(!data.is_full());
+        assert_eq!((data.remaining_capacity(), data.fill_ratio()), (Some(4), 0.0));
+        for i in 1..=3 {
+            data.push(i);
+        }
+        assert!(!data.is_full());
+        assert_eq!((data.remaining_capacity(), data.fill_ratio()), (Some(1), 0.75));
+        data.push(4);
+        assert!(data.is_full());
+        assert_eq!((data.remaining_capacity(), data.fill_ratio()), (Some(0), 1.0));
+        data.push(5);
+        assert!(data.is_full());
+        assert_eq!((data.remaining_capacity(), data.fill_ratio()), (Some(0), 1.0));
+        data.pop_oldest();
+        assert!(!data.is_full());
+        assert_eq!(data.remaining_capacity(), Some(1));
+
+        let mut data = RollingBuffer::<i32>::new(0);
+        data.extend(0..10);
+        assert!(!data.is_full());
+        assert_eq!((data.remaining_capacity(), data.fill_ratio()), (None, 0.0));
+    }
+
+    #[test]
+    fn test_as_raw_slice() {
+        let mut data = RollingBuffer::<i32>::new(5);
+        for i in 0..4 {
+            data.push(i);
+        }
+        assert_eq!(data.as_raw_slice(), [0, 1, 2, 3]);
+        for i in 4..7 {
+            data.push(i);
+        }
+        assert_eq!(data.as_raw_slice(), [5, 6, 2, 3, 4]);
+        assert_eq!(data.as_raw_slice(), data.raw());
+    }
+
+    #[test]
+    fn test_boxed_slots() {
+        /// The fields of a RollingBuffer<u64> back when a full buffer kept its Vec and a separate `len`.
+        #[allow(dead_code)]
+        struct VecSlots {
+            size: usize,
+            vec: Vec<u64>,
+            last_removed: Option<u64>,
+            count: u64,
+            discard_removed: bool,
+            #[cfg(feature = "zeroize")]
+            wipe_removed: Option<fn(&mut u64)>,
+            len: usize,
+            head: usize,
+            mask: Option<usize>,
+        }
+        assert!(size_of::<RollingBuffer<u64>>() < size_of::<VecSlots>());
+
+        let mut data = RollingBuffer::<u64>::new(5);
+        data.extend(0..4);
+        assert!(data.capacity() >= 4);
+        data.push(4);
+        assert_eq!(data.capacity(), 5);
+        data.extend(5..20);
+        assert_eq!((data.capacity(), data.raw()), (5, &[15, 16, 17, 18, 19][..]));
+        assert_eq!(data.pop(), Some(19));
+        assert_eq!((data.capacity(), data.raw()), (5, &[15, 16, 17, 18][..]));
+        data.push(20);
+        assert_eq!((data.capacity(), data.to_vec()), (5, vec![15, 16, 17, 18, 20]));
+
+        let data = RollingBuffer::from_vec(3, (0..100).collect());
+        assert_eq!((data.capacity(), data.to_vec()), (3, vec![97, 98, 99]));
+        let mut data = RollingBuffer::<u64>::new(8);
+        data.extend(0..8);
+        data.resize(3);
+        assert_eq!((data.capacity(), data.to_vec()), (3, vec![5, 6, 7]));
+    }
+
+    #[test]
+    fn test_power_of_two_and_other_sizes() {
+        use std::collections::VecDeque;
+
+        // powers of two index with a mask, the other sizes with a modulo
+        for size in 1..=17 {
+            let mut data = RollingBuffer::<usize>::new(size);
+            let mut model = VecDeque::new();
+            for step in 0..100 {
+                if step % 5 == 4 {
+                    assert_eq!(data.pop_oldest(), model.pop_front());
+                } else {
+                    data.push(step);
+                    model.push_back(step);
+                    if model.len() > size {
+                        model.pop_front();
+                    }
+                }
+                assert_eq!(data.to_vec(), Vec::from(model.clone()));
+                assert_eq!((data.first(), data.last()), (model.front(), model.back()));
+                let start = data.live_range().start;
+                for (i, value) in model.iter().enumerate() {
+                    assert_eq!(data.get(start + i), Some(value));
+                }
+            }
+            data.resize(size + 1);
+            data.extend(100..100 + size);
+            model.extend(100..100 + size);
+            let expected: Vec<_> = model.iter().copied().skip(model.len().saturating_sub(size + 1)).collect();
+            assert_eq!(data.to_vec(), expected);
+        }
+    }
+
+    /// The `count % size` indexing the buffer used before it kept an explicit head.
+    struct CountIndexed {
+        size: usize,
+        slots: Vec<i32>,
+        allocated: usize,
+        count: usize,
+        len: usize,
+        last_removed: Option<i32>,
+        popped: bool,
+    }
+
+    impl CountIndexed {
+        fn new(size: usize) -> Self {
+            Self { size, slots: vec![0; size], allocated: 0, count: 0, len: 0, last_removed: None, popped: false }
+        }
+
+        fn push(&mut self, value: i32) {
+            let index = self.count % self.size;
+            if index < self.allocated {
+                let removed = std::mem::replace(&mut self.slots[index], value);
+                if self.len == self.size {
+                    self.last_removed = Some(removed);
+                }
+            } else {
+                self.slots[index] = value;
+                self.allocated += 1;
+            }
+            self.count += 1;
+            self.len = (self.len + 1).min(self.size);
+        }
+
+        fn pop(&mut self) -> Option<i32> {
+            if self.len == 0 {
+                return None;
+            }
+            self.count -= 1;
+            self.len -= 1;
+            self.popped = true;
+            Some(std::mem::take(&mut self.slots[self.count % self.size]))
+        }
+
+        fn pop_oldest(&mut self) -> Option<i32> {
+            if self.len == 0 {
+                return None;
+            }
+            self.len -= 1;
+            self.popped = true;
+            Some(std::mem::take(&mut self.slots[(self.count - self.len - 1) % self.size]))
+        }
+
+        fn get(&self, i: usize) -> Option<&i32> {
+            (i < self.count && i >= self.count - self.len).then(|| &self.slots[i % self.size])
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    enum Op {
+        Push(i32),
+        Pop,
+        PopOldest,
+        Extend(Vec<i32>),
+        ExtendFromSlice(Vec<i32>),
+        Clear,
+    }
+
+    fn op() -> impl proptest::strategy::Strategy<Value = Op> {
+        use proptest::prelude::*;
+        prop_oneof![
+            6 => any::<i32>().prop_map(Op::Push),
+            1 => Just(Op::Pop),
+            1 => Just(Op::PopOldest),
+            1 => proptest::collection::vec(any::<i32>(), 0..20).prop_map(Op::Extend),
+            1 => proptest::collection::vec(any::<i32>(), 0..20).prop_map(Op::ExtendFromSlice),
+            1 => Just(Op::Clear),
+        ]
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_head_matches_count_indexing(size in 1usize..9, ops in proptest::collection::vec(op(), 0..60)) {
+            let mut data = RollingBuffer::<i32>::new(size);
+            let mut reference = CountIndexed::new(size);
+            for op in ops {
+                match op {
+                    Op::Push(value) => {
+                        data.push(value);
+                        reference.push(value);
+                    }
+                    Op::Pop => proptest::prop_assert_eq!(data.pop(), reference.pop()),
+                    Op::PopOldest => proptest::prop_assert_eq!(data.pop_oldest(), reference.pop_oldest()),
+                    Op::Extend(values) => {
+                        data.extend(values.iter().copied());
+                        values.into_iter().for_each(|value| reference.push(value));
+                    }
+                    Op::ExtendFromSlice(values) => {
+                        data.extend_from_slice(&values);
+                        values.into_iter().for_each(|value| reference.push(value));
+                    }
+                    Op::Clear => {
+                        data.clear();
+                        reference = CountIndexed::new(size);
+                    }
+                }
+                proptest::prop_assert_eq!((data.count(), data.len()), (reference.count, reference.len));
+                if !reference.popped {
+                    // popping makes the buffer contiguous, until then the slots are laid out by count
+                    proptest::prop_assert_eq!(data.raw(), &reference.slots[..reference.allocated]);
+                }
+                proptest::prop_assert_eq!(data.last_removed(), &reference.last_removed);
+                for i in reference.count.saturating_sub(size + 1)..reference.count + 1 {
+                    proptest::prop_assert_eq!(data.get(i), reference.get(i));
+                }
+                let live: Vec<i32> = (reference.count - reference.len..reference.count).map(|i| reference.slots[i % size]).collect();
+                proptest::prop_assert_eq!(data.to_vec(), live.clone());
+                proptest::prop_assert_eq!((data.first(), data.last()), (live.first(), live.last()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_count_near_32_bit_boundary() {
+        let start = u32::MAX as usize - 2;
+        let mut data = RollingBuffer::from_raw_parts(vec![1, 2, 3, 4], 4, start, None).unwrap();
+        for i in 5..=10 {
+            data.push(i);
+        }
+        assert_eq!(data.total_pushed(), u32::MAX as u64 + 4);
+        assert_eq!(data.to_vec(), [7, 8, 9, 10]);
+        assert_eq!((data.first(), data.last(), data.get_back(3)), (Some(&7), Some(&10), Some(&7)));
+        assert_eq!(data.oldest_index(), Some(u32::MAX as usize));
+    }
+
+    #[test]
+    fn test_logical_indices_wrap_around() {
+        // what a 32-bit target goes through after 2^32 pushes, at the usize::MAX boundary of this one
+        let mut data = RollingBuffer::from_raw_parts(vec![1, 2, 3, 4], 4, usize::MAX - 3, None).unwrap();
+        for i in 5..=9 {
+            data.push(i);
+        }
+        assert_eq!(data.count(), 1);
+        let live = data.live_range();
+        assert_eq!((live.start, live.end), (usize::MAX - 2, 1));
+        assert_eq!(data.to_vec(), [6, 7, 8, 9]);
+        assert_eq!((data.first(), data.last()), (Some(&6), Some(&9)));
+        assert_eq!((data.get(usize::MAX - 2), data.get(usize::MAX), data.get(0)), (Some(&6), Some(&8), Some(&9)));
+        assert_eq!(data[usize::MAX - 1], 7);
+        assert_eq!(data.get_checked(usize::MAX - 3), Err(GetError::Evicted { index: usize::MAX - 3, oldest_live: usize::MAX - 2 }));
+        assert_eq!(data.get_checked(1), Err(GetError::NotYetPushed { index: 1, next: 1 }));
+        assert_eq!(data.get_back(0), Some(&9));
+        assert_eq!(
+            data.iter_indexed().map(|(i, &x)| (i, x)).collect::<Vec<_>>(),
+            [(usize::MAX - 2, 6), (usize::MAX - 1, 7), (usize::MAX, 8), (0, 9)]
+        );
+        assert_eq!(data.iter_from(usize::MAX).copied().collect::<Vec<_>>(), [8, 9]);
+        assert_eq!(data.range(std::ops::Range { start: usize::MAX - 1, end: 1 }).copied().collect::<Vec<_>>(), [7, 8, 9]);
+        assert_eq!(data.range(..).count(), 4);
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert!(RollingBuffer::<u64>::try_new(usize::MAX).is_err());
+
+        let mut data = RollingBuffer::<i32>::try_new(4).unwrap();
+        let mut reference = RollingBuffer::<i32>::new(4);
+        assert_eq!(data.capacity(), 4);
+        for i in 0..7 {
+            data.push(i);
+            reference.push(i);
+            assert_eq!(data.to_vec(), reference.to_vec());
+        }
+        assert_eq!((data.raw(), data.last_removed()), (reference.raw(), reference.last_removed()));
+        assert_eq!(data.capacity(), 4);
+
+        assert_eq!(
+            RollingBuffer::<i32>::builder().size(1_000_000).max_size(1000).build().unwrap_err(),
+            BuildError::SizeTooLarge { size: 1_000_000, max: 1000 }
+        );
+        assert!(RollingBuffer::<i32>::builder().size(1000).max_size(1000).build().is_ok());
+    }
+
+    #[test]
+    fn test_without_default() {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Reading {
+            sensor: String,
+            value: f64,
+        }
+        let reading = |value: f64| Reading { sensor: "probe".to_string(), value };
+
+        let mut data = RollingBuffer::<Reading>::new(3);
+        assert!(data.is_empty());
+        for i in 0..5 {
+            data.push(reading(i as f64));
+        }
+        assert_eq!(data.raw().len(), 3);
+        assert_eq!(data.len(), 3);
+        assert_eq!(data.first(), Some(&reading(2.0)));
+        assert_eq!(data.last(), Some(&reading(4.0)));
+        assert_eq!(data.get(3), Some(&reading(3.0)));
+        assert_eq!(data.last_removed(), &Some(reading(1.0)));
+        assert_eq!(data.iter().map(|r| r.value).collect::<Vec<_>>(), [2.0, 3.0, 4.0]);
+        data.iter_mut().for_each(|r| r.value *= 10.0);
+        assert_eq!(data.to_vec(), [reading(20.0), reading(30.0), reading(40.0)]);
+
+        let mut data = (0..4).map(|i| reading(i as f64)).collect_rolling(2);
+        data.extend([reading(9.0)]);
+        data.resize(3);
+        data.push(reading(10.0));
+        assert_eq!(data.into_vec(), [reading(3.0), reading(9.0), reading(10.0)]);
+    }
+
+    #[test]
+    fn test_without_clone() {
+        let mut data = RollingBuffer::<Box<dyn Fn() -> i32>>::new(2);
+        for i in 0..3 {
+            data.push(Box::new(move || i * 10));
+        }
+        assert_eq!(data.len(), 2);
+        assert_eq!(data.get(1).map(|f| f()), Some(10));
+        assert_eq!(data.last().map(|f| f()), Some(20));
+        assert_eq!(data.last_removed().as_ref().map(|f| f()), Some(0));
+        assert_eq!(data.iter().map(|f| f()).collect::<Vec<_>>(), [10, 20]);
+
+        #[derive(Debug, PartialEq)]
+        struct Handle(u32);
+
+        let mut data = (0..5).map(Handle).collect_rolling(3);
+        data.extend([Handle(5)]);
+        data.resize(2);
+        data.get_mut(5).unwrap().0 += 1;
+        assert_eq!(data.into_vec(), [Handle(4), Handle(6)]);
+
+        let mut data = (0..6).map(Handle).collect_rolling(4);
+        assert_eq!((data.pop(), data.pop_oldest()), (Some(Handle(5)), Some(Handle(2))));
+        data.extend([Handle(4), Handle(8), Handle(9)]);
+        data.dedup();
+        data.retain(|handle| handle.0 != 8);
+        assert_eq!((data.len(), data.count(), data.last_removed()), (2, 8, &Some(Handle(3))));
+        data.extend([Handle(10), Handle(11), Handle(12)]);
+        data.keep_last(3);
+        data.truncate_back(2);
+        assert_eq!((data.get(8), data.get(9), data.count()), (Some(&Handle(10)), Some(&Handle(11)), 10));
+        assert_eq!(data.drain().collect::<Vec<_>>(), [Handle(10), Handle(11)]);
+        assert!(data.is_empty());
+    }
+
+    /// Everything a `RollingRead` implementation exposes, compared across implementations.
+    #[derive(Debug, PartialEq)]
+    struct Observed {
+        len: usize,
+        count: u64,
+        last_removed: Option<i32>,
+        live: Vec<i32>,
+        fill: (bool, Option<usize>, f64),
+        window: Vec<Option<i32>>,
+    }
+
+    fn observe<B: RollingRead<i32>>(data: &B) -> Observed {
+        assert_eq!(data.count(), data.total_pushed() as usize);
+        Observed {
+            len: data.len(),
+            count: data.total_pushed(),
+            last_removed: *data.last_removed(),
+            live: data.iter().copied().collect(),
+            fill: (data.is_full(), data.remaining_capacity(), data.fill_ratio()),
+            window: (data.count().saturating_sub(data.len() + 1)..data.count() + 1).map(|i| data.get(i).copied()).collect(),
+        }
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_deque_matches_buffer(size in 0usize..9, ops in proptest::collection::vec(op(), 0..60)) {
+            let mut data = RollingBuffer::<i32>::new(size);
+            let mut deque = RollingDeque::<i32>::new(size);
+            for op in ops {
+                match op {
+                    Op::Push(value) => {
+                        data.push(value);
+                        deque.push(value);
+                    }
+                    Op::Pop => proptest::prop_assert_eq!(data.pop(), deque.pop_back()),
+                    Op::PopOldest => proptest::prop_assert_eq!(data.pop_oldest(), deque.pop_front()),
+                    Op::Extend(values) => {
+                        data.extend(values.iter().copied());
+                        values.into_iter().for_each(|value| deque.push(value));
+                    }
+                    Op::ExtendFromSlice(values) => {
+                        data.extend_from_slice(&values);
+                        values.into_iter().for_each(|value| deque.push(value));
+                    }
+                    Op::Clear => {
+                        data.clear();
+                        deque.clear();
+                    }
+                }
+                proptest::prop_assert_eq!(observe(&data), observe(&deque));
+                proptest::prop_assert_eq!(data.to_vec(), deque.to_vec());
+                proptest::prop_assert_eq!((data.first(), data.last()), (deque.first(), deque.last()));
+                proptest::prop_assert_eq!((data.get_back(0), data.get_back(1)), (deque.get_back(0), deque.get_back(1)));
+            }
+        }
+    }
+
+    /// Runs the trait-level operations of `ops` against a RollingBuffer and `B`, comparing after every step.
+    fn matches_buffer<B: Rolling<i32>>(size: usize, ops: &[Op]) -> Result<(), proptest::test_runner::TestCaseError> {
+        let mut data = RollingBuffer::<i32>::new(size);
+        let mut other = B::new(size);
+        for op in ops {
+            match op {
+                Op::Push(value) => {
+                    data.push(*value);
+                    other.push(*value);
+                }
+                Op::Extend(values) | Op::ExtendFromSlice(values) => {
+                    data.extend_from_slice(values);
+                    values.iter().for_each(|value| other.push(*value));
+                }
+                Op::Clear => {
+                    data.clear();
+                    other.clear();
+                }
+                Op::Pop | Op::PopOldest => {}
+            }
+            proptest::prop_assert_eq!(observe(&data), observe(&other));
+            proptest::prop_assert_eq!(data.to_vec(), other.to_vec());
+            proptest::prop_assert_eq!((data.first(), data.last()), (other.first(), other.last()));
+            if let Some(last) = other.last_mut() {
+                *last += 1;
+                *data.last_mut().unwrap() += 1;
+            }
+        }
+        Ok(())
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_array_matches_buffer(ops in proptest::collection::vec(op(), 0..60)) {
+            matches_buffer::<RollingArray<i32, 1>>(1, &ops)?;
+            matches_buffer::<RollingArray<i32, 3>>(3, &ops)?;
+            matches_buffer::<RollingArray<i32, 4>>(4, &ops)?;
+            matches_buffer::<RollingArray<i32, 7>>(7, &ops)?;
+            matches_buffer::<RollingDeque<i32>>(3, &ops)?;
+            matches_buffer::<RollingDeque<i32>>(0, &ops)?;
+        }
+    }
+
+    #[cfg(feature = "heapless")]
+    proptest::proptest! {
+        #[test]
+        fn test_heapless_matches_buffer(ops in proptest::collection::vec(op(), 0..60)) {
+            use crate::buffer::heapless::RollingHeapless;
+            matches_buffer::<RollingHeapless<i32, 1>>(1, &ops)?;
+            matches_buffer::<RollingHeapless<i32, 4>>(4, &ops)?;
+            matches_buffer::<RollingHeapless<i32, 5>>(5, &ops)?;
+        }
+    }
+
+    #[test]
+    fn test_dyn_rolling() {
+        let mut data: Box<dyn Rolling<i32>> = Box::new(RollingBuffer::new(3));
+        assert!(data.is_empty());
+        assert_eq!(data.remaining_capacity(), Some(3));
+        for i in 1..=4 {
+            data.push(i);
+        }
+        assert!(!data.is_empty());
+        assert!(data.is_full());
+        assert_eq!(data.remaining_capacity(), Some(0));
+        assert_eq!(data.fill_ratio(), 1.0);
+        assert_eq!(data.len(), 3);
+        assert_eq!(data.size(), 3);
+        assert_eq!(data.count(), 4);
+        assert_eq!(data.total_pushed(), 4);
+        assert_eq!(data.to_vec(), [2, 3, 4]);
+        assert_eq!(data.last_removed(), &Some(1));
+        assert_eq!(data.get(1), Some(&2));
+        assert_eq!(data.get_back(0), Some(&4));
+        assert_eq!(data.first(), Some(&2));
+        assert_eq!(data.last(), Some(&4));
+
+        *data.get_mut(2).unwrap() += 10;
+        *data.get_back_mut(1).unwrap() += 10;
+        *data.first_mut().unwrap() *= 2;
+        *data.last_mut().unwrap() *= 2;
+        assert_eq!(data.to_vec(), [4, 23, 8]);
+        data.iter_mut().for_each(|x| *x -= 1);
+        assert_eq!(data.iter().copied().collect::<Vec<_>>(), [3, 22, 7]);
+
+        let buffers: Vec<Box<dyn Rolling<i32>>> = vec![Box::new(RollingBuffer::new(0)), Box::new(RollingBuffer::new(2))];
+        let kept: Vec<usize> = buffers
+            .into_iter()
+            .map(|mut b| {
+                b.push(1);
+                b.push(2);
+                b.push(3);
+                b.len()
+            })
+            .collect();
+        assert_eq!(kept, [3, 2]);
+    }
+
+    #[test]
+    fn test_read_write_split() {
+        fn mean(data: &impl RollingRead<f64>) -> Option<f64> {
+            (!data.is_empty()).then(|| data.iter().sum::<f64>() / data.len() as f64)
+        }
+        fn fill<B: Rolling<f64>>(size: usize, values: &[f64]) -> B {
+            let mut data = B::new(size);
+            for &value in values {
+                data.push(value);
+            }
+            *data.last_mut().unwrap() += 1.0;
+            data
+        }
+        fn reset(data: &mut dyn RollingWrite<f64>) {
+            data.clear();
+        }
+
+        let mut data: RollingBuffer<f64> = fill(3, &[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(data.to_vec(), [2.0, 3.0, 5.0]);
+        assert_eq!(mean(&data), Some(10.0 / 3.0));
+        reset(&mut data);
+        assert_eq!(mean(&data), None);
+        assert_eq!(data.count(), 0);
+    }
+
+    #[test]
+    fn test_rolling_array() {
+        let mut data = RollingArray::<f32, 3>::default();
+        assert!(data.is_empty());
+        assert_eq!(data.size(), 3);
+        for value in [1.0, 2.0, 3.0, 4.0, 5.0] {
+            data.push(value);
+        }
+        assert_eq!(data.as_raw_slice(), [4.0, 5.0, 3.0]);
+        assert_eq!(data.iter().copied().collect::<Vec<_>>(), [3.0, 4.0, 5.0]);
+        assert_eq!(data.iter().sum::<f32>() / data.len() as f32, 4.0);
+        assert_eq!((data.get(1), data.get(2), data.get(4)), (None, Some(&3.0), Some(&5.0)));
+        assert_eq!((data.first(), data.last()), (Some(&3.0), Some(&5.0)));
+        assert_eq!(data.last_removed(), &Some(2.0));
+        assert_eq!(data.count(), 5);
+
+        data.clear();
+        assert_eq!(data.as_raw_slice(), [0.0; 3]);
+        assert_eq!(data.get(4), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "the size of a RollingArray is fixed to 3")]
+    fn test_rolling_array_size_mismatch() {
+        <RollingArray<i32, 3> as RollingWrite<i32>>::new(4);
+    }
+
+    #[test]
+    fn test_copy_fast_paths() {
+        let values: Vec<f64> = (0..23).map(|i| i as f64 * 0.5).collect();
+        for size in [0, 1, 4, 7, 8, 30] {
+            for split in [0, 3, 11, 23] {
+                let mut bulk = RollingBuffer::<f64>::new(size);
+                let mut pushed = RollingBuffer::<f64>::new(size);
+                bulk.extend_from_slice(&values[..split]);
+                bulk.pop_oldest();
+                bulk.extend_from_slice(&values[split..]);
+                values[..split].iter().for_each(|&x| pushed.push(x));
+                pushed.pop_oldest();
+                values[split..].iter().for_each(|&x| pushed.push(x));
+                assert_eq!(bulk.raw(), pushed.raw());
+                assert_eq!((bulk.count(), bulk.len(), bulk.last_removed()), (pushed.count(), pushed.len(), pushed.last_removed()));
+
+                let mut out = vec![-1.0; bulk.len()];
+                bulk.copy_to_slice(&mut out);
+                assert_eq!(out, bulk.iter().copied().collect::<Vec<_>>());
+                assert_eq!(out, bulk.to_vec());
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "destination length doesn't match the buffer length")]
+    fn test_copy_to_slice_length_mismatch() {
+        let data: RollingBuffer<u64> = (0..10).collect_rolling(4);
+        data.copy_to_slice(&mut [0; 3]);
+    }
+
+    #[cfg(feature = "allocator_api")]
+    #[test]
+    fn test_buffer_new_in_counting_allocator() {
+        use std::alloc::{AllocError, Allocator, Global, Layout};
+        use std::cell::Cell;
+        use std::ptr::NonNull;
+
+        #[derive(Default)]
+        struct Counting {
+            allocations: Cell<usize>,
+            deallocations: Cell<usize>,
+        }
+
+        unsafe impl Allocator for Counting {
+            fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+                self.allocations.set(self.allocations.get() + 1);
+                Global.allocate(layout)
+            }
+
+            unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+                self.deallocations.set(self.deallocations.get() + 1);
+                unsafe { Global.deallocate(ptr, layout) }
+            }
+        }
+
+        let arena = Counting::default();
+        let mut data = RollingBuffer::new_in(4, &arena);
+        assert_eq!(arena.allocations.get(), 0);
+        for i in 0..100 {
+            data.push(i);
+        }
+        assert_eq!(arena.allocations.get(), 1);
+        assert_eq!(*data.raw(), [96, 97, 98, 99]);
+        assert_eq!(data.to_vec(), [96, 97, 98, 99]);
+        assert_eq!((data.get(97), data.last_removed(), data.count()), (Some(&97), &Some(95), 100));
+        assert!(std::ptr::eq(*data.allocator(), &arena));
+
+        let copies = Counting::default();
+        assert_eq!(*data.to_vec_in(&copies), [96, 97, 98, 99]);
+        assert_eq!((copies.allocations.get(), copies.deallocations.get()), (1, 1));
+
+        data.clear();
+        data.push(7);
+        assert_eq!(data.pop(), Some(7));
+        assert_eq!(arena.allocations.get(), 1);
+        drop(data);
+        assert_eq!(arena.deallocations.get(), 1);
+
+        let mut unbounded = RollingBuffer::new_in(0, &arena);
+        unbounded.extend(0..20);
+        assert!(arena.allocations.get() > 2);
+        assert_eq!(unbounded, (0..20).collect::<Vec<_>>());
+        let mut global = RollingBuffer::<i32>::new(2);
+        global.extend([20, 21, 22]);
+        assert_eq!(unbounded.zip_map(&global, |a, b| a * b).collect::<Vec<_>>(), [18 * 21, 19 * 22]);
+        assert_eq!(global.dot(&unbounded), 796.0);
+        unbounded.append(&mut global);
+        assert_eq!((unbounded.len(), unbounded.last(), global.is_empty()), (22, Some(&22), true));
+        drop(unbounded);
+        assert_eq!(arena.allocations.get(), arena.deallocations.get());
+
+        let data = <RollingBuffer<i32, Global> as RollingWrite<i32>>::new(3);
+        assert_eq!((data.size(), data.len(), data.capacity()), (3, 0, 0));
+    }
+
+    #[test]
+    fn test_eq_logical_contents() {
+        let mut wrapped = RollingBuffer::<i32>::new(4);
+        wrapped.extend(1..=10);
+        let seeded = RollingBuffer::from_vec(4, vec![7, 8, 9, 10]);
+        assert_ne!(wrapped.raw(), seeded.raw());
+        assert_ne!((wrapped.count(), wrapped.last_removed()), (seeded.count(), seeded.last_removed()));
+        assert_eq!(wrapped, seeded);
+
+        let mut larger = RollingBuffer::<i32>::new(6);
+        larger.extend(7..=10);
+        assert_eq!(wrapped, larger);
+        assert_eq!(wrapped, RollingBuffer::from(vec![7, 8, 9, 10]));
+
+        larger.push(11);
+        assert_ne!(wrapped, larger);
+        wrapped.pop_oldest();
+        assert_ne!(wrapped, seeded);
+        assert_eq!(RollingBuffer::<i32>::new(3), RollingBuffer::<i32>::new(5));
+    }
+
+    #[test]
+    fn test_eq_sequences() {
+        let mut data = RollingBuffer::<i32>::new(4);
+        data.extend(1..=6);
+        assert_eq!(data, [3, 4, 5, 6]);
+        assert_eq!(data, vec![3, 4, 5, 6]);
+        assert_eq!(data, [3, 4, 5, 6][..]);
+        assert_eq!(data, &[3, 4, 5, 6][..]);
+        assert_eq!([3, 4, 5, 6], data);
+        assert_eq!(vec![3, 4, 5, 6], data);
+        assert_eq!([3, 4, 5, 6][..], data);
+        assert_eq!(&[3, 4, 5, 6][..], data);
+        assert_ne!(data, [3, 4, 5]);
+        assert_ne!(data, [6, 3, 4, 5]);
+        assert_ne!(data, [3, 4, 5, 6, 7]);
+
+        data.pop_oldest();
+        assert_eq!(data, [4, 5, 6]);
+        assert_eq!(RollingBuffer::<i32>::new(2), [0; 0]);
+
+        let names = RollingBuffer::from_vec(2, vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+        assert_eq!(names, ["b", "c"]);
+        assert_eq!(vec!["b", "c"], names);
+    }
+
+    #[test]
+    fn test_hash_logical_contents() {
+        use std::collections::HashMap;
+        use std::hash::{BuildHasher, RandomState};
+
+        let state = RandomState::new();
+        let mut wrapped = RollingBuffer::<i32>::new(3);
+        wrapped.extend(1..=8);
+        let fresh = RollingBuffer::from_vec(5, vec![6, 7, 8]);
+        assert_eq!(wrapped, fresh);
+        assert_eq!(state.hash_one(&wrapped), state.hash_one(&fresh));
+        assert_ne!(state.hash_one(&wrapped), state.hash_one(RollingBuffer::from_vec(3, vec![5, 6, 7])));
+
+        let mut cache = HashMap::new();
+        cache.insert(wrapped.clone(), "seen");
+        assert_eq!(cache.get(&fresh), Some(&"seen"));
+        wrapped.push(9);
+        assert_eq!(cache.get(&wrapped), None);
+    }
+
+    #[test]
+    fn test_ord_logical_contents() {
+        use std::cmp::Ordering;
+        use std::collections::BTreeMap;
+
+        let mut wrapped = RollingBuffer::<i32>::new(3);
+        wrapped.extend(1..=5);
+        let fresh = RollingBuffer::from_vec(3, vec![3, 4, 5]);
+        assert_eq!(wrapped.cmp(&fresh), Ordering::Equal);
+
+        let prefix = RollingBuffer::from_vec(2, vec![3, 4]);
+        let longer = RollingBuffer::from_vec(4, vec![3, 4, 5, 0]);
+        let higher = RollingBuffer::from_vec(3, vec![3, 5, 0]);
+        assert!(prefix < wrapped);
+        assert!(wrapped < longer);
+        assert!(longer < higher);
+        assert!(RollingBuffer::<i32>::new(3) < prefix);
+        assert_eq!(wrapped.cmp(&higher), [3, 4, 5].cmp(&[3, 5, 0]));
+        assert_eq!(longer.cmp(&prefix), vec![3, 4, 5, 0].cmp(&vec![3, 4]));
+
+        let floats = RollingBuffer::from_vec(2, vec![1.0, f64::NAN]);
+        assert_eq!(floats.partial_cmp(&floats), None);
+
+        let mut patterns = BTreeMap::new();
+        for window in [wrapped, fresh, prefix, higher] {
+            *patterns.entry(window).or_insert(0) += 1;
+        }
+        assert_eq!(patterns.values().copied().collect::<Vec<_>>(), [1, 2, 1]);
+    }
+
+    #[test]
+    fn test_display() {
+        let mut data = RollingBuffer::<f64>::new(3);
+        assert_eq!(data.to_string(), "[]");
+        data.extend([0.25, 0.5, 1.0, 2.0]);
+        assert_eq!(data.to_string(), "[0.5, 1, 2]");
+        assert_eq!(format!("{data:.1}"), "[0.5, 1.0, 2.0]");
+        assert_eq!(format!("{data:>5.2}"), "[ 0.50,  1.00,  2.00]");
+        assert_eq!(format!("{data:+}"), "[+0.5, +1, +2]");
+
+        let mut unbounded: RollingBuffer<&str> = RollingBuffer::new(0);
+        assert_eq!(unbounded.to_string(), "[]");
+        unbounded.extend(["a", "b"]);
+        assert_eq!(format!("{unbounded:<2}"), "[a , b ]");
+    }
+
+    #[test]
+    fn test_debug() {
+        let mut data = RollingBuffer::<i32>::new(4);
+        data.extend(1..=6);
+        assert_eq!(
+            format!("{data:?}"),
+            "RollingBuffer { size: 4, count: 6, len: 4, head: 2, data: [3, 4, 5, 6], last_removed: Some(2) }"
+        );
+        data.pop_oldest();
+        assert_eq!(
+            format!("{data:#?}"),
+            "RollingBuffer {
+    size: 4,
+    count: 6,
+    len: 3,
+    head: 0,
+    data: [
+        4,
+        5,
+        6,
+    ],
+    last_removed: Some(
+        2,
+    ),
+}"
+        );
+        let unbounded = RollingBuffer::from(vec!["a"]);
+        assert_eq!(
+            format!("{unbounded:?}"),
+            r#"RollingBuffer { size: 0, count: 1, len: 1, head: 0, data: ["a"], last_removed: None }"#
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        fn check(data: RollingBuffer<f64>) {
+            let json: RollingBuffer<f64> = serde_json::from_str(&serde_json::to_string(&data).unwrap()).unwrap();
+            let binary: RollingBuffer<f64> = bincode::deserialize(&bincode::serialize(&data).unwrap()).unwrap();
+            for mut restored in [json, binary] {
+                let mut data = data.clone();
+                assert_eq!((restored.size(), restored.count(), restored.len()), (data.size(), data.count(), data.len()));
+                assert_eq!(restored.last_removed(), data.last_removed());
+                for i in data.count().saturating_sub(data.len() + 1)..data.count() + 1 {
+                    assert_eq!(restored.get(i), data.get(i));
+                }
+                restored.push(100.0);
+                data.push(100.0);
+                assert_eq!(restored.last_removed(), data.last_removed());
+                assert_eq!(restored, data);
+            }
+        }
+
+        let mut wrapped = RollingBuffer::new(4);
+        wrapped.extend((0..10).map(f64::from));
+        assert_eq!(
+            serde_json::to_string(&wrapped).unwrap(),
+            r#"{"size":4,"count":10,"track_removed":true,"last_removed":5.0,"data":[6.0,7.0,8.0,9.0]}"#
+        );
+        check(wrapped.clone());
+        wrapped.pop_oldest();
+        check(wrapped);
+
+        let mut underfilled = RollingBuffer::new(5);
+        underfilled.extend([1.5, 2.5]);
+        check(underfilled);
+
+        let mut unbounded = RollingBuffer::new(0);
+        unbounded.extend([1.0, 2.0, 3.0]);
+        unbounded.pop_oldest();
+        check(unbounded);
+        check(RollingBuffer::new(3));
+
+        let untracked = RollingBuffer::builder().size(2).prefill([1.0, 2.0, 3.0]).track_removed(false).build().unwrap();
+        let json = serde_json::to_string(&untracked).unwrap();
+        let mut restored: RollingBuffer<f64> = serde_json::from_str(&json).unwrap();
+        assert!(!restored.tracks_removed());
+        restored.push(4.0);
+        assert_eq!(restored.last_removed(), &None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_rejects_invalid_state() {
+        let too_long = r#"{"size":2,"count":3,"track_removed":true,"last_removed":null,"data":[1,2,3]}"#;
+        let error = serde_json::from_str::<RollingBuffer<i32>>(too_long).unwrap_err();
+        assert!(error.to_string().starts_with("3 live elements don't fit into a buffer of size 2"));
+
+        let low_count = r#"{"size":0,"count":1,"track_removed":true,"last_removed":null,"data":[1,2]}"#;
+        let error = serde_json::from_str::<RollingBuffer<i32>>(low_count).unwrap_err();
+        assert!(error.to_string().starts_with("count 1 is below the 2 live elements"));
+
+        assert!(serde_json::from_str::<RollingBuffer<i32>>(r#"{"size":2,"data":[1]}"#).is_err());
+        assert!(serde_json::from_str::<RollingBuffer<i32>>(r#"[1, 2]"#).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_keeps_64_bit_count() {
+        let state = r#"{"size":4,"count":18446744073709551615,"track_removed":true,"last_removed":0,"data":[1,2,3,4]}"#;
+        let mut restored = serde_json::from_str::<RollingBuffer<i32>>(state).unwrap();
+        assert_eq!((restored.total_pushed(), restored.to_vec()), (u64::MAX, vec![1, 2, 3, 4]));
+        assert_eq!(serde_json::to_string(&restored).unwrap(), state);
+        restored.push(5);
+        assert_eq!((restored.total_pushed(), restored.last_removed()), (0, &Some(1)));
+        assert_eq!(restored, [2, 3, 4, 5]);
+
+        let unbounded = r#"{"size":0,"count":4294967296,"track_removed":true,"last_removed":null,"data":[7]}"#;
+        let restored = serde_json::from_str::<RollingBuffer<i32>>(unbounded).unwrap();
+        assert_eq!((restored.total_pushed(), restored.to_vec()), (1 << 32, vec![7]));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_logical_serde() {
+        use serde::de::DeserializeSeed;
+
+        use crate::buffer::logical_serde::{self, LogicalSeed, LogicalSerde};
+
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Dashboard {
+            name: String,
+            #[serde(serialize_with = "logical_serde::serialize", deserialize_with = "logical_serde::deserialize::<4, _, _>")]
+            history: RollingBuffer<u32>,
+        }
+
+        let mut history = RollingBuffer::new(4);
+        history.extend(1..=6);
+        let json = serde_json::to_string(&Dashboard { name: "cpu".to_string(), history }).unwrap();
+        assert_eq!(json, r#"{"name":"cpu","history":[3,4,5,6]}"#);
+
+        let dashboard: Dashboard = serde_json::from_str(r#"{"name":"cpu","history":[1,2,3,4,5,6,7]}"#).unwrap();
+        assert_eq!(dashboard.history, [4, 5, 6, 7]);
+        assert_eq!((dashboard.history.size(), dashboard.history.count(), dashboard.history.last_removed()), (4, 7, &Some(3)));
+
+        let mut de = serde_json::Deserializer::from_str("[1.5, 2.5]");
+        let short = LogicalSeed::<f64>::new(3).deserialize(&mut de).unwrap();
+        assert_eq!((short.len(), short.size()), (2, 3));
+        assert_eq!(serde_json::to_string(&LogicalSerde(&short)).unwrap(), "[1.5,2.5]");
+
+        let mut de = serde_json::Deserializer::from_str(r#"{"a": 1}"#);
+        assert!(LogicalSeed::<u32>::new(2).deserialize(&mut de).is_err());
+    }
+
+    /// The regime a generated buffer is in: empty, unbounded, underfilled, full or wrapped.
+    #[cfg(any(feature = "proptest", feature = "quickcheck"))]
+    fn regime(buffer: &RollingBuffer<u8>) -> usize {
+        assert_eq!(buffer.iter().count(), buffer.len());
+        assert_eq!(buffer.last_removed().is_some(), buffer.total_pushed() > buffer.len() as u64);
+        match (buffer.size(), buffer.len(), buffer.count()) {
+            (_, 0, 0) => 0,
+            (0, len, count) => {
+                assert_eq!(len, count);
+                1
+            }
+            (size, len, count) if len < size => {
+                assert_eq!(len, count);
+                2
+            }
+            (size, len, count) => {
+                assert_eq!(len, size);
+                if count == size { 3 } else { 4 }
+            }
+        }
+    }
+
+    #[cfg(feature = "proptest")]
+    #[test]
+    fn test_proptest_arbitrary_covers_regimes() {
+        use proptest::prelude::*;
+        use proptest::strategy::ValueTree;
+        use proptest::test_runner::TestRunner;
+
+        let mut runner = TestRunner::deterministic();
+        let mut seen = [false; 5];
+        for _ in 0..500 {
+            let mut tree = any::<RollingBuffer<u8>>().new_tree(&mut runner).unwrap();
+            let buffer = tree.current();
+            let expected = regime(&buffer);
+            seen[expected] = true;
+            while tree.simplify() {
+                let simpler = tree.current();
+                let simpler_regime = regime(&simpler);
+                assert!(simpler_regime < expected || (simpler_regime == expected && simpler.size() <= buffer.size()));
+            }
+        }
+        assert_eq!(seen, [true; 5]);
+    }
+
+    #[cfg(feature = "quickcheck")]
+    #[test]
+    fn test_quickcheck_arbitrary_covers_regimes() {
+        use quickcheck::{Arbitrary, Gen};
+
+        let mut g = Gen::new(100);
+        let mut seen = [false; 5];
+        for _ in 0..500 {
+            let buffer = RollingBuffer::<u8>::arbitrary(&mut g);
+            seen[regime(&buffer)] = true;
+            for smaller in buffer.shrink() {
+                regime(&smaller);
+                assert!(smaller.size() <= buffer.size() && smaller.count() <= buffer.count());
+                assert!(smaller != buffer || smaller.count() < buffer.count() || smaller.size() < buffer.size());
+            }
+        }
+        assert_eq!(seen, [true; 5]);
+    }
+
+    /// Key material stand-in that records every value it had when it was zeroized.
+    #[cfg(feature = "zeroize")]
+    #[derive(Debug, Clone, Default)]
+    struct Secret(u8, std::rc::Rc<std::cell::RefCell<Vec<u8>>>);
+
+    #[cfg(feature = "zeroize")]
+    impl zeroize::Zeroize for Secret {
+        fn zeroize(&mut self) {
+            self.1.borrow_mut().push(self.0);
+            self.0.zeroize();
+        }
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_zeroize_removed() {
+        let wiped = std::rc::Rc::default();
+        let secret = |value| Secret(value, std::rc::Rc::clone(&wiped));
+
+        let mut buffer = RollingBuffer::new(3);
+        buffer.extend((1..=4).map(secret));
+        assert_eq!(buffer.last_removed().as_ref().map(|s| s.0), Some(1));
+        buffer.zeroize_removed();
+        assert!(buffer.last_removed().is_none() && !buffer.tracks_removed());
+        assert_eq!(*wiped.borrow(), [1]);
+
+        buffer.push(secret(5));
+        buffer.extend_from_slice(&(6..=10).map(secret).collect::<Vec<_>>());
+        assert!(buffer.last_removed().is_none());
+        // the bulk copy wipes the whole ring in physical order, 6 is skipped without being stored
+        // and the final push of 10 evicts the 7 written by the same call
+        assert_eq!(*wiped.borrow(), [1, 2, 4, 5, 3, 7]);
+        buffer.resize(1);
+        assert_eq!(*wiped.borrow(), [1, 2, 4, 5, 3, 7, 8, 9]);
+        assert_eq!(buffer.iter().map(|s| s.0).collect::<Vec<_>>(), [10]);
+        assert_eq!(buffer.raw().len(), 1);
+
+        // the popped slot is refilled without a wipe, only the push of 12 evicts a live element
+        assert_eq!(buffer.pop().map(|s| s.0), Some(10));
+        buffer.extend_from_slice(&[secret(11), secret(12)]);
+        assert_eq!(*wiped.borrow(), [1, 2, 4, 5, 3, 7, 8, 9, 11]);
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_zeroize() {
+        use zeroize::{Zeroize, Zeroizing};
+
+        let mut buffer = RollingBuffer::new(4);
+        buffer.extend([1u8, 2, 3, 4, 5, 6]);
+        buffer.pop();
+        buffer.zeroize();
+        assert_eq!((buffer.len(), buffer.count(), buffer.size()), (0, 0, 4));
+        assert!(buffer.raw().is_empty() && buffer.last_removed().is_none());
+        buffer.push(7);
+        assert_eq!(buffer, [7]);
+
+        let wiped = std::rc::Rc::default();
+        let mut buffer = RollingBuffer::new(2);
+        buffer.extend((1..=5).map(|value| Secret(value, std::rc::Rc::clone(&wiped))));
+        buffer.pop();
+        drop(Zeroizing::new(buffer));
+        assert_eq!(*wiped.borrow(), [4, 3]);
+    }
+
+    #[cfg(feature = "rkyv")]
+    #[test]
+    fn test_rkyv_round_trip() {
+        use rkyv::rancor::Error;
+
+        use crate::buffer::archived::ArchivedRollingBuffer;
+
+        let mut floats = RollingBuffer::new(5);
+        floats.extend((1..=12).map(|i| i as f32 / 2.0));
+        floats.push(7.0);
+        floats.pop_oldest();
+        let bytes = rkyv::to_bytes::<Error>(&floats).unwrap();
+        let archived = rkyv::access::<ArchivedRollingBuffer<rkyv::Archived<f32>>, Error>(&bytes).unwrap();
+        assert_eq!(archived.as_slice(), [5.0, 5.5, 6.0, 7.0].map(rkyv::Archived::<f32>::from_native));
+        assert_eq!((archived.size(), archived.count(), archived.len()), (5, 13, 4));
+        assert_eq!(archived.get(9).map(|x| x.to_native()), Some(5.0));
+        assert_eq!(archived.get(8), None);
+        assert_eq!(archived.last_removed().map(|x| x.to_native()), Some(4.0));
+        let restored = rkyv::deserialize::<RollingBuffer<f32>, Error>(archived).unwrap();
+        assert_eq!(restored.to_vec(), floats.to_vec());
+        assert_eq!((restored.count(), restored.last_removed(), restored.get(9)), (13, &Some(4.0), Some(&5.0)));
+
+        let mut words = RollingBuffer::builder().size(3).track_removed(false).build().unwrap();
+        words.extend(["a", "bb", "ccc", "dddd", "eeeee"].map(String::from));
+        assert!(!words.as_slices().1.is_empty());
+        let bytes = rkyv::to_bytes::<Error>(&words).unwrap();
+        let restored = rkyv::from_bytes::<RollingBuffer<String>, Error>(&bytes).unwrap();
+        assert_eq!(restored.to_vec(), words.to_vec());
+        assert_eq!((restored.count(), restored.last_removed(), restored.tracks_removed()), (5, &None, false));
+        let mut pushed = restored;
+        pushed.push("f".to_string());
+        assert_eq!(pushed, ["dddd", "eeeee", "f"]);
+
+        let unbounded: RollingBuffer<u16> = (0..100).collect();
+        let bytes = rkyv::to_bytes::<Error>(&unbounded).unwrap();
+        assert_eq!(rkyv::from_bytes::<RollingBuffer<u16>, Error>(&bytes).unwrap().to_vec(), unbounded.to_vec());
+
+        let mut bytes = rkyv::to_bytes::<Error>(&floats).unwrap().to_vec();
+        assert!(rkyv::access::<ArchivedRollingBuffer<rkyv::Archived<f32>>, Error>(&bytes[..bytes.len() - 1]).is_err());
+        // size is the first field of the root, which rkyv places at the end
+        let root = bytes.len() - size_of::<ArchivedRollingBuffer<rkyv::Archived<f32>>>();
+        bytes[root..root + 4].copy_from_slice(&2u32.to_le_bytes());
+        let mut aligned = rkyv::util::AlignedVec::<16>::new();
+        aligned.extend_from_slice(&bytes);
+        let err = rkyv::from_bytes::<RollingBuffer<f32>, Error>(&aligned).unwrap_err();
+        assert_eq!(err.to_string(), "vec of length 4 exceeds buffer size 2");
+    }
+
+    #[test]
+    fn test_sum_mean() {
+        use crate::buffer::stats::RollingStats;
+
+        let mut data = RollingBuffer::new(4);
+        assert_eq!((data.sum(), data.mean()), (0, None));
+        data.extend([1i32, 2, 3, 4, 5, 6]);
+        assert_eq!(data.as_slices(), (&[3, 4][..], &[5, 6][..]));
+        assert_eq!((data.sum(), data.mean()), (18, Some(4.5)));
+
+        // the padding of an underfilled buffer and the slots freed by pop() don't count
+        let mut bytes = RollingBuffer::new_filled(3, 0u8);
+        bytes.extend([200, 250, 255, 7]);
+        bytes.pop_oldest();
+        assert_eq!((bytes.sum(), bytes.mean()), (262u128, Some(131.0)));
+
+        let mut floats = RollingBuffer::new(3);
+        floats.extend([5.0f64, 1e16, 1.0, -1e16]);
+        assert_eq!(floats.iter().sum::<f64>(), 0.0);
+        assert_eq!(floats.sum(), 1.0);
+        let singles: RollingDeque<f32> = [0.1f32; 10].into_iter().fold(RollingDeque::new(8), |mut deque, x| {
+            deque.push(x);
+            deque
+        });
+        assert!((singles.mean().unwrap() - 0.1f32 as f64).abs() < 1e-15);
+        let mut array = RollingArray::<u64, 2>::default();
+        array.push(u64::MAX);
+        array.push(u64::MAX);
+        assert_eq!(array.sum(), 2 * u64::MAX as u128);
+
+        let dynamic: &dyn RollingRead<i32> = &data;
+        assert_eq!(dynamic.mean(), Some(4.5));
+    }
+
+    #[test]
+    fn test_min_max() {
+        use crate::buffer::stats::RollingStats;
+
+        let mut data = RollingBuffer::new(5);
+        assert_eq!(data.minmax_indexed(), None);
+        data.extend([9.0, 0.5, 3.0, f64::NAN, 7.0, 2.0, 8.0, 2.0]);
+        assert_eq!(data.as_slices().1, [2.0, 8.0, 2.0]);
+        // the oldest of the two 2.0 wins, the NaN at index 3 is skipped
+        assert_eq!(data.min_indexed(), Some((5, &2.0)));
+        assert_eq!(data.max_indexed(), Some((6, &8.0)));
+        assert_eq!(data.minmax_indexed(), Some(((5, &2.0), (6, &8.0))));
+        assert_eq!(data.get(5), Some(&2.0));
+
+        let mut nan = RollingBuffer::new(2);
+        nan.extend([1.0, f64::NAN, f64::NAN]);
+        assert_eq!((nan.min_indexed(), nan.minmax_indexed()), (None, None));
+
+        let mut ints = RollingBuffer::new(4);
+        ints.extend([4, 6, 5, 7, 3]);
+        ints.pop_oldest();
+        ints.pop();
+        assert_eq!(ints.raw(), &[5, 7]);
+        assert_eq!(ints.min_indexed(), Some((2, &5)));
+        assert_eq!(ints.max_indexed(), Some((3, &7)));
+        assert_eq!(ints.minmax_indexed(), Some(((2, &5), (3, &7))));
+    }
+
+    #[test]
+    fn test_min_max_indexed_on_ord_buffer() {
+        use crate::buffer::stats::RollingStats;
+
+        let mut data: RollingBuffer<i32> = RollingBuffer::new(3);
+        data.extend([4, 9, 1, 6]);
+        assert_eq!((data.min_indexed(), data.max_indexed()), (Some((2, &1)), Some((1, &9))));
+        // the buffer is Ord itself, so min() and max() still compare two buffers
+        let other = RollingBuffer::from(vec![9, 1, 7]);
+        assert_eq!(data.clone().min(other.clone()), data);
+        assert_eq!(data.clone().max(other.clone()), other);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_minmax_tracker_matches_scan(
+            size in 0usize..12,
+            start in proptest::collection::vec(-4i8..4, 0..30),
+            values in proptest::collection::vec(proptest::option::weighted(0.9, -4i8..4), 0..200),
+        ) {
+            use crate::buffer::{minmax::RollingMinMax, stats::RollingStats};
+
+            let mut tracker = RollingMinMax::from_buffer(start.iter().map(|&x| f64::from(x)).collect_rolling(size));
+            for value in values {
+                tracker.push(value.map_or(f64::NAN, f64::from));
+                // the same element, not just an equal value, so ties resolve to the oldest one in both
+                let scan = (tracker.min_indexed().map(|(_, x)| x as *const f64), tracker.max_indexed().map(|(_, x)| x as *const f64));
+                proptest::prop_assert_eq!((tracker.current_min().map(|x| x as *const f64), tracker.current_max().map(|x| x as *const f64)), scan);
+            }
+            tracker.clear();
+            proptest::prop_assert_eq!((tracker.current_min(), tracker.current_max(), tracker.len()), (None, None, 0));
+        }
+    }
+
+    /// Deterministic xorshift64 samples, uniform in `low..high`, for the drift checks of the running stats.
+    fn uniform(low: f64, high: f64) -> impl FnMut() -> f64 {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 11) as f64 / (1u64 << 53) as f64 * (high - low) + low
+        }
+    }
+
+    #[test]
+    fn test_running_sum() {
+        use crate::buffer::{running::RollingSum, stats::RollingStats};
+
+        let mut ints = RollingSum::new(3);
+        assert_eq!((ints.sum(), ints.mean()), (0, None));
+        for (value, sum) in [(200u8, 200), (250, 450), (255, 705), (1, 506), (2, 258)] {
+            ints.push(value);
+            assert_eq!(ints.sum(), sum);
+        }
+        assert_eq!(ints.mean(), Some(86.0));
+        let untracked = RollingBuffer::builder().size(2).prefill([-5i64, 7, 9]).track_removed(false).build().unwrap();
+        let mut from_buffer = RollingSum::from_buffer(untracked);
+        from_buffer.push(1);
+        assert_eq!((from_buffer.sum(), from_buffer.to_vec()), (10, vec![9, 1]));
+        from_buffer.clear();
+        assert_eq!((from_buffer.sum(), from_buffer.mean()), (0, None));
+
+        let mut next = uniform(-4.99e5, 5.01e5);
+        let mut drifting = RollingSum::new(1000);
+        let mut recomputed = RollingSum::new(1000).recompute_every(1000);
+        for _ in 0..2_000_000 {
+            let value = next();
+            drifting.push(value);
+            recomputed.push(value);
+        }
+        let exact = RollingStats::sum(&*drifting);
+        assert_eq!(exact, RollingStats::sum(&*recomputed));
+        assert!((drifting.sum() - exact).abs() < 1e-3, "{} vs {exact}", drifting.sum());
+        assert!((recomputed.sum() - exact).abs() < 1e-6, "{} vs {exact}", recomputed.sum());
+        assert!((recomputed.mean().unwrap() - exact / 1000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_variance() {
+        use crate::buffer::{running::RollingVariance, stats::RollingStats};
+
+        let mut buffer = RollingBuffer::<f64>::new(4);
+        assert_eq!(buffer.variance(), None);
+        buffer.push(3.5);
+        assert_eq!(buffer.variance(), Some(0.0));
+        for value in [1.0, 2.0, 3.0, 4.0, 5.0, 6.0] {
+            buffer.push(value);
+        }
+        assert_eq!(buffer.to_vec(), [3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(buffer.variance(), Some(1.25));
+        #[cfg(feature = "std")]
+        assert_eq!(buffer.std_dev(), Some(1.25f64.sqrt()));
+        let ints = RollingBuffer::builder().size(3).prefill([2u32, 4, 4, 4]).build().unwrap();
+        assert_eq!(ints.variance(), Some(0.0));
+
+        // large values with a small spread, where the sum of squares cancels out
+        let mut large = RollingBuffer::new(4);
+        let mut running = RollingVariance::new(4);
+        assert_eq!((running.mean(), running.variance()), (None, None));
+        for value in [1e9 + 4.0, 1e9 + 7.0, 1e9 + 13.0, 1e9 + 16.0, 1e9 + 4.0, 1e9 + 7.0] {
+            large.push(value);
+            running.push(value);
+        }
+        assert_eq!(large.variance(), Some(22.5));
+        assert!((running.variance().unwrap() - 22.5).abs() < 1e-6, "{:?}", running.variance());
+        assert_eq!(running.mean(), Some(1e9 + 10.0));
+
+        let untracked = RollingBuffer::builder().size(2).prefill([1i32, 5, 9]).track_removed(false).build().unwrap();
+        let mut from_buffer = RollingVariance::from_buffer(untracked);
+        assert_eq!(from_buffer.variance(), Some(4.0));
+        from_buffer.push(9);
+        assert_eq!((from_buffer.mean(), from_buffer.variance()), (Some(9.0), Some(0.0)));
+        from_buffer.clear();
+        assert_eq!(from_buffer.variance(), None);
+
+        let mut next = uniform(1e6, 1.001e6);
+        let mut drifting = RollingVariance::new(500);
+        let mut recomputed = RollingVariance::new(500).recompute_every(500);
+        for _ in 0..1_000_000 {
+            let value = next();
+            drifting.push(value);
+            recomputed.push(value);
+        }
+        let exact = RollingStats::variance(&*drifting).unwrap();
+        assert!((drifting.variance().unwrap() - exact).abs() < 1e-3 * exact, "{:?} vs {exact}", drifting.variance());
+        assert!((recomputed.variance().unwrap() - exact).abs() < 1e-9 * exact, "{:?} vs {exact}", recomputed.variance());
+    }
+
+    #[test]
+    fn test_quantile() {
+        use crate::buffer::stats::RollingStats;
+
+        let mut buffer = RollingBuffer::<f64>::new(10);
+        assert_eq!((buffer.quantile(0.5), buffer.quantiles(&[0.5])), (None, None));
+        buffer.extend([4.0, 1.0, f64::NAN, 3.0, 2.0]);
+        assert_eq!(buffer.quantile(0.0), Some(1.0));
+        assert_eq!(buffer.quantile(0.25), Some(1.0));
+        assert_eq!(buffer.quantile(0.26), Some(2.0));
+        assert_eq!(buffer.quantile(0.5), Some(2.0));
+        assert_eq!(buffer.quantile(1.0), Some(4.0));
+        assert_eq!((buffer.quantile(-0.1), buffer.quantile(1.5), buffer.quantile(f64::NAN)), (None, None, None));
+        assert_eq!(buffer.quantiles(&[1.0, 0.0, 0.75]), Some(vec![4.0, 1.0, 3.0]));
+        assert_eq!(buffer.quantiles(&[0.5, 2.0]), None);
+        assert_eq!(buffer.quantiles(&[]), Some(vec![]));
+
+        let mut wrapped = RollingBuffer::new(4);
+        wrapped.extend([100, 9, 7, 8, 6]);
+        assert_eq!(wrapped.raw(), &[6, 9, 7, 8]);
+        assert_eq!(wrapped.quantiles(&[0.0, 0.5, 0.51, 1.0]), Some(vec![6, 7, 8, 9]));
+        assert_eq!(wrapped.to_vec(), [9, 7, 8, 6]);
+        let mut popped = RollingBuffer::new(4);
+        popped.extend([5, 1, 3]);
+        popped.pop_oldest();
+        assert_eq!((popped.quantile(0.0), popped.quantile(1.0)), (Some(1), Some(3)));
+    }
+
+    #[test]
+    fn test_ema() {
+        use crate::buffer::running::RollingEma;
+
+        // weighted mean with weights (1 - alpha)^age, straight from the definition
+        fn reference(values: &[f64], alpha: f64) -> f64 {
+            let mut weighted = 0.0;
+            let mut weight = 0.0;
+            for (age, value) in values.iter().rev().enumerate() {
+                weighted += (1.0 - alpha).powi(age as i32) * value;
+                weight += (1.0 - alpha).powi(age as i32);
+            }
+            weighted / weight
+        }
+
+        let values = [10.0, 12.0, 9.0, 15.0, 11.0, 30.0, 8.0, 10.0, 11.0, 12.0, 14.0, 9.0];
+        let mut ema = RollingEma::new(4, 0.4);
+        assert_eq!((ema.value(), ema.samples(), ema.is_warmed_up()), (None, 0, false));
+        for (n, &value) in values.iter().enumerate() {
+            ema.push(value);
+            let expected = reference(&values[..=n], 0.4);
+            assert!((ema.value().unwrap() - expected).abs() < 1e-12, "{:?} vs {expected}", ema.value());
+            assert_eq!(ema.is_warmed_up(), n + 1 >= 4);
+        }
+        assert_eq!(ema.value().map(|value| value == reference(&values[..1], 0.4)), Some(false));
+        assert_eq!((ema.samples(), ema.to_vec()), (12, vec![11.0, 12.0, 14.0, 9.0]));
+
+        // once warmed up it tracks the usual recursive EMA seeded with the first value
+        let mut seeded = values[0];
+        let mut long = RollingEma::new(8, 0.1);
+        for n in 0..500 {
+            let value = values[n % values.len()];
+            long.push(value);
+            seeded += 0.1 * (value - seeded);
+        }
+        assert!((long.value().unwrap() - seeded).abs() < 1e-9);
+
+        let buffer = RollingBuffer::builder().size(3).prefill([1u16, 2, 3, 4]).build().unwrap();
+        let mut attached = RollingEma::from_buffer(buffer, 0.5);
+        assert_eq!((attached.samples(), attached.value()), (3, Some(reference(&[2.0, 3.0, 4.0], 0.5))));
+        assert_eq!(attached.alpha(), 0.5);
+        assert!(attached.is_warmed_up());
+        attached.push(6);
+        assert_eq!(attached.value(), Some(reference(&[2.0, 3.0, 4.0, 6.0], 0.5)));
+        attached.clear();
+        assert_eq!((attached.value(), attached.samples(), attached.is_empty()), (None, 0, true));
+        assert_eq!(RollingEma::<u8>::new(2, 1.0).value(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "the smoothing factor 0 is not in (0, 1]")]
+    fn test_ema_invalid_alpha() {
+        crate::buffer::running::RollingEma::<f64>::new(2, 0.0);
+    }
+
+    #[test]
+    fn test_weighted_mean() {
+        use crate::buffer::stats::RollingStats;
+
+        let mut buffer = RollingBuffer::new(4);
+        assert_eq!((buffer.weighted_mean(&[]), buffer.linear_weighted_mean()), (None, None));
+        buffer.extend([50i32, 1, 2, 3, 10]);
+        assert_eq!(buffer.raw(), &[10, 1, 2, 3]);
+        assert_eq!(buffer.weighted_mean(&[0.0, 0.0, 0.0, 1.0]), Some(10.0));
+        assert_eq!(buffer.weighted_mean(&[1.0, 0.0, 0.0, 0.0]), Some(1.0));
+        assert_eq!(buffer.weighted_mean(&[1.0; 4]), buffer.mean());
+        assert_eq!(buffer.weighted_mean(&[1.0, 2.0, 3.0, 4.0]), Some(5.4));
+        assert_eq!(buffer.linear_weighted_mean(), Some(5.4));
+        assert_eq!(buffer.weighted_mean(&[1.0, 2.0, 3.0]), None);
+        assert_eq!(buffer.weighted_mean(&[1.0, -1.0, 0.0, 0.0]), None);
+
+        let weights = [0.5, 3.0, 1.25, 2.0];
+        let reversed_weights: Vec<f64> = weights.iter().rev().copied().collect();
+        let mut reversed = RollingBuffer::new(4);
+        reversed.extend(buffer.iter().rev().copied());
+        assert_eq!(reversed.to_vec(), [10, 3, 2, 1]);
+        assert_eq!(reversed.weighted_mean(&reversed_weights), buffer.weighted_mean(&weights));
+        assert_eq!(reversed.linear_weighted_mean(), Some(2.6));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_zscore_last() {
+        use crate::buffer::stats::RollingStats;
+
+        let mut buffer = RollingBuffer::<f32>::new(4);
+        assert_eq!((buffer.zscore_last(false), buffer.zscore_last(true)), (None, None));
+        buffer.extend([1.0, 3.0]);
+        assert_eq!((buffer.zscore_last(false), buffer.zscore_last(true)), (Some(1.0), None));
+        buffer.extend([5.0, 5.0, 5.0, 5.0]);
+        assert_eq!((buffer.zscore_last(false), buffer.zscore_last(true)), (None, None));
+
+        // wrapped: logical order is [2, 4, 6, 12]
+        let mut wrapped = RollingBuffer::new(4);
+        wrapped.extend([100.0f64, 2.0, 4.0, 6.0, 12.0]);
+        assert_eq!(wrapped.zscore_last(true), Some(8.0 / (8.0f64 / 3.0).sqrt()));
+        let whole = wrapped.zscore_last(false).unwrap();
+        assert!((whole - (12.0 - wrapped.mean().unwrap()) / wrapped.std_dev().unwrap()).abs() < 1e-12);
+        assert!(whole < wrapped.zscore_last(true).unwrap());
+        wrapped.push(-10.0);
+        assert!(wrapped.zscore_last(true).unwrap() < -2.0);
+    }
+
+    #[test]
+    fn test_histogram() {
+        use crate::buffer::stats::RollingStats;
+
+        let mut buffer = RollingBuffer::new(8);
+        assert_eq!(buffer.histogram(4), None);
+        buffer.extend([-50.0, 0.0, 1.0, 2.5, 4.0, 9.9, 10.0, f64::NAN, 5.0]);
+        assert_eq!(buffer.histogram(0), None);
+        let histogram = buffer.histogram(4).unwrap();
+        assert_eq!(histogram.edges(), [0.0, 2.5, 5.0, 7.5, 10.0]);
+        assert_eq!(histogram.counts(), [2, 2, 1, 2]);
+        assert_eq!((histogram.underflow(), histogram.overflow()), (0, 0));
+
+        let custom = buffer.histogram_with_edges(&[1.0, 5.0, 9.9]).unwrap();
+        assert_eq!(custom.edges(), [1.0, 5.0, 9.9]);
+        assert_eq!(custom.counts(), [3, 2]);
+        assert_eq!((custom.underflow(), custom.overflow()), (1, 1));
+        assert_eq!(buffer.histogram_with_edges(&[1.0]), None);
+        assert_eq!(buffer.histogram_with_edges(&[1.0, 1.0, 2.0]), None);
+        assert_eq!(buffer.histogram_with_edges(&[1.0, f64::NAN]), None);
+
+        let constant = RollingBuffer::builder().size(3).prefill([7u8, 7, 7, 7]).build().unwrap();
+        let histogram = constant.histogram(2).unwrap();
+        assert_eq!((histogram.edges(), histogram.counts()), (&[6.5, 7.0, 7.5][..], &[0, 3][..]));
+        // ±0.5 rounds back to 1e17, the range is widened by a relative step instead
+        let huge = RollingBuffer::builder().size(3).prefill([1e17; 3]).build().unwrap();
+        let histogram = huge.histogram(1).unwrap();
+        assert!(histogram.edges()[0] < 1e17 && 1e17 < histogram.edges()[1]);
+        assert_eq!(histogram.counts(), [3]);
+        assert_eq!(RollingBuffer::from_vec(2, vec![f64::NAN]).histogram(2), None);
+        let empty = RollingBuffer::<u8>::new(3).histogram_with_edges(&[0.0, 1.0]).unwrap();
+        assert_eq!((empty.counts(), empty.underflow(), empty.overflow()), (&[0][..], 0, 0));
+    }
+
+    #[test]
+    fn test_top_k() {
+        use crate::buffer::stats::RollingStats;
+
+        let mut buffer = RollingBuffer::new(5);
+        assert!(buffer.top_k(3).is_empty());
+        buffer.extend([(9, 'a'), (3, 'b'), (7, 'c'), (3, 'd'), (1, 'e'), (7, 'f')]);
+        assert_eq!(buffer.top_k(0), Vec::<&(i32, char)>::new());
+        assert_eq!(buffer.top_k(2), [&(7, 'f'), &(7, 'c')]);
+        let by_key: Vec<char> = buffer.top_k_by_key(3, |record| record.0).into_iter().map(|record| record.1).collect();
+        assert_eq!(by_key, ['c', 'f', 'b']);
+        assert_eq!(buffer.top_k_by_key(100, |record| record.0).len(), 5);
+        assert_eq!(buffer.to_vec(), [(3, 'b'), (7, 'c'), (3, 'd'), (1, 'e'), (7, 'f')]);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_top_k_matches_sort(size in 1usize..12, values in proptest::collection::vec(0u8..6, 0..40), k in 0usize..14) {
+            use crate::buffer::stats::RollingStats;
+
+            let mut buffer = RollingBuffer::new(size);
+            buffer.extend(values.iter().copied().enumerate());
+            let mut sorted: Vec<&(usize, u8)> = buffer.iter().collect();
+            sorted.sort_by_key(|record| core::cmp::Reverse(record.1));
+            sorted.truncate(k);
+            proptest::prop_assert_eq!(buffer.top_k_by_key(k, |record| record.1), sorted);
+            let mut sorted: Vec<&(usize, u8)> = buffer.iter().collect();
+            sorted.sort_by(|a, b| b.cmp(a));
+            sorted.truncate(k);
+            proptest::prop_assert_eq!(buffer.top_k(k), sorted);
+        }
+    }
+
+    #[test]
+    fn test_fold_for_each_mut() {
+        let mut buffer = RollingBuffer::new(4);
+        assert_eq!(buffer.fold(0, |acc, value: &i32| acc + value), 0);
+        buffer.extend([1, 2, 3, 4, 5, 6]);
+        assert_eq!(buffer.raw(), &[5, 6, 3, 4]);
+        let visited = buffer.fold(Vec::new(), |mut visited, &value| {
+            visited.push(value);
+            visited
+        });
+        assert_eq!(visited, buffer.to_vec());
+        let mut order = Vec::new();
+        buffer.for_each_mut(|value| {
+            order.push(*value);
+            *value *= 10;
+        });
+        assert_eq!(order, [3, 4, 5, 6]);
+        assert_eq!(buffer.to_vec(), [30, 40, 50, 60]);
+
+        let mut popped = RollingBuffer::new(5);
+        popped.extend([1, 2, 3, 4, 5, 6, 7]);
+        popped.pop_oldest();
+        popped.pop();
+        assert_eq!(popped.fold(String::new(), |acc, value| acc + &value.to_string()), "456");
+        popped.for_each_mut(|value| *value = -*value);
+        assert_eq!(popped.to_vec(), [-4, -5, -6]);
+        let mut unbounded = RollingBuffer::new(0);
+        unbounded.extend(["a", "b"]);
+        assert_eq!(unbounded.fold(String::new(), |acc, value| acc + value), "ab");
+    }
+
+    #[test]
+    fn test_search() {
+        let mut buffer = RollingBuffer::new(5);
+        assert_eq!((buffer.contains(&1), buffer.position(|_| true), buffer.rposition(|_| true)), (false, None, None));
+        buffer.extend([7, 1, 2, 9, 3, 4, 5]);
+        // physical [4, 5, 2, 9, 3]: the first run is [2, 9, 3], the second [4, 5]
+        assert_eq!(buffer.as_slices(), (&[2, 9, 3][..], &[4, 5][..]));
+        assert!(buffer.contains(&4) && buffer.contains(&3) && !buffer.contains(&7) && !buffer.contains(&1));
+        assert_eq!(buffer.position(|&value| value == 3), Some(4));
+        assert_eq!(buffer.position(|&value| value == 4), Some(5));
+        assert_eq!(buffer.position(|&value| value == 7), None);
+        let boundary = buffer.position(|&value| value > 3).unwrap();
+        assert_eq!(boundary, 3);
+        assert_eq!((buffer.get(boundary - 1), buffer.get(boundary + 1)), (Some(&2), Some(&3)));
+        let boundary = buffer.rposition(|&value| value < 4).unwrap();
+        assert_eq!(boundary, 4);
+        assert_eq!((buffer.get(boundary - 1), buffer[boundary], buffer.get(boundary + 1)), (Some(&9), 3, Some(&4)));
+        assert_eq!(buffer.rposition(|&value| value > 3), Some(6));
+        assert_eq!(buffer.rposition(|&value| value == 9), Some(3));
+        assert_eq!(buffer.count_matching(|&value| value % 2 == 1), 3);
+        assert_eq!(buffer.count_matching(|&value| value > 100), 0);
+
+        let mut popped = RollingBuffer::new(3);
+        popped.extend([0, 1, 2]);
+        popped.pop_oldest();
+        assert!(!popped.contains(&0));
+        assert_eq!((popped.position(|&value| value == 1), popped.count_matching(|&value| value == 0)), (Some(1), 0));
+    }
+
+    #[test]
+    fn test_binary_search() {
+        let mut buffer = RollingBuffer::new(5);
+        assert_eq!((buffer.binary_search(&3), buffer.partition_point(|&value| value < 3)), (Err(0), 0));
+        buffer.extend([10, 20, 30, 40, 50, 60, 70]);
+        // logical indices 2..7 hold [30, 40, 50, 60, 70], the first run is [30, 40, 50], the second [60, 70]
+        assert_eq!(buffer.as_slices(), (&[30, 40, 50][..], &[60, 70][..]));
+        assert_eq!(buffer.binary_search(&5), Err(2));
+        assert_eq!(buffer.binary_search(&30), Ok(2));
+        assert_eq!(buffer.binary_search(&45), Err(4));
+        assert_eq!(buffer.binary_search(&50), Ok(4));
+        assert_eq!(buffer.binary_search(&55), Err(5));
+        assert_eq!(buffer.binary_search(&60), Ok(5));
+        assert_eq!(buffer.binary_search(&70), Ok(6));
+        assert_eq!(buffer.binary_search(&99), Err(7));
+        assert_eq!(buffer.binary_search_by(|value| value.cmp(&40)), Ok(3));
+        for (target, point) in [(0, 2), (30, 2), (31, 3), (50, 4), (51, 5), (60, 5), (70, 6), (71, 7)] {
+            assert_eq!(buffer.partition_point(|&value| value < target), point, "{target}");
+        }
+        let found = buffer.binary_search(&60).unwrap();
+        assert_eq!((buffer.get(found - 1), buffer.get(found)), (Some(&50), Some(&60)));
+
+        let mut unwrapped = RollingBuffer::new(0);
+        unwrapped.extend([1, 3, 3, 5]);
+        assert_eq!(unwrapped.partition_point(|&value| value < 3), 1);
+        assert_eq!(unwrapped.partition_point(|&value| value <= 3), 3);
+        assert!(matches!(unwrapped.binary_search(&3), Ok(1 | 2)));
+        assert_eq!(unwrapped.binary_search(&4), Err(3));
+    }
+
+    #[test]
+    fn test_zip_map_dot() {
+        let mut a = RollingBuffer::new(4);
+        let mut b = RollingBuffer::new(6);
+        assert_eq!((a.zip_map(&b, |x: &i32, y: &f64| *x as f64 * y).count(), a.dot(&b)), (0, 0.0));
+        a.extend([9, 9, 1, 2, 3, 4]);
+        b.extend([0.5, 2.0, 3.0]);
+        // a is wrapped with logical [1, 2, 3, 4], b underfilled with [0.5, 2.0, 3.0]
+        assert_eq!(a.as_slices(), (&[1, 2][..], &[3, 4][..]));
+        let pairs: Vec<(i32, f64)> = a.zip_map(&b, |x, y| (*x, *y)).collect();
+        assert_eq!(pairs, [(2, 0.5), (3, 2.0), (4, 3.0)]);
+        assert_eq!(a.zip_map(&b, |_, _| ()).len(), 3);
+        assert_eq!(a.dot(&b), 19.0);
+        assert_eq!(b.dot(&a), 19.0);
+
+        b.extend([5.0, 6.0, 7.0, 8.0]);
+        // b now wraps as well, with logical [2.0, 3.0, 5.0, 6.0, 7.0, 8.0] and a different count
+        assert_eq!((a.count(), b.count()), (6, 7));
+        let pairs: Vec<(i32, f64)> = a.zip_map(&b, |x, y| (*x, *y)).collect();
+        assert_eq!(pairs, [(1, 5.0), (2, 6.0), (3, 7.0), (4, 8.0)]);
+        assert_eq!(a.dot(&b), 70.0);
+        let differences: Vec<f64> = b.zip_map(&a, |y, x| y - *x as f64).collect();
+        assert_eq!(differences, [4.0; 4]);
+    }
+
+    #[test]
+    fn test_apply_kernel() {
+        let mut buffer = RollingBuffer::new(5);
+        buffer.extend([1.0, 2.0]);
+        assert_eq!(buffer.apply_kernel(&[0.5, 0.25, 0.25]), None);
+        assert_eq!(buffer.apply_kernel(&[]), Some(0.0));
+        buffer.extend([4.0, 8.0, 16.0, 32.0]);
+        // logical [2, 4, 8, 16, 32] with the newest two in the second run
+        assert_eq!(buffer.as_slices(), (&[2.0, 4.0, 8.0, 16.0][..], &[32.0][..]));
+        assert_eq!(buffer.apply_kernel(&[1.0]), Some(32.0));
+        assert_eq!(buffer.apply_kernel(&[1.0, 0.1, 0.01]), Some(32.0 + 1.6 + 0.08));
+        assert_eq!(buffer.apply_kernel(&[1.0, -1.0]), Some(16.0));
+        assert_eq!(buffer.apply_kernel(&[0.0, 0.0, 0.0, 0.0, 1.0]), Some(2.0));
+        assert_eq!(buffer.apply_kernel(&[1.0; 6]), None);
+
+        let kernel = [0.5, 0.5];
+        let history: Vec<f64> = (0..=3).rev().map(|offset| buffer.apply_kernel_at(offset, &kernel).unwrap()).collect();
+        assert_eq!(history, [3.0, 6.0, 12.0, 24.0]);
+        assert_eq!(buffer.apply_kernel_at(0, &kernel), buffer.apply_kernel(&kernel));
+        assert_eq!((buffer.apply_kernel_at(4, &kernel), buffer.apply_kernel_at(usize::MAX, &kernel)), (None, None));
+        assert_eq!(buffer.apply_kernel_at(4, &[2.0]), Some(4.0));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_rms() {
+        use crate::buffer::{running::RollingRms, stats::RollingStats};
+
+        let mut buffer = RollingBuffer::<f32>::new(4);
+        assert_eq!(buffer.rms(), None);
+        buffer.extend([3.0, -4.0]);
+        // the two unused slots are not counted
+        assert_eq!(buffer.rms(), Some(12.5f64.sqrt()));
+        buffer.extend([1.0, 1.0, 1.0, 1.0]);
+        assert_eq!(buffer.rms(), Some(1.0));
+        buffer.pop_oldest();
+        assert_eq!(buffer.rms(), Some(1.0));
+
+        let mut running = RollingRms::new(3);
+        assert_eq!((running.mean_square(), running.rms()), (None, None));
+        for (value, mean_square) in [(2.0, 4.0), (-2.0, 4.0), (4.0, 8.0), (0.0, 20.0 / 3.0), (1.0, 17.0 / 3.0)] {
+            running.push(value);
+            assert!((running.mean_square().unwrap() - mean_square).abs() < 1e-12);
+        }
+        assert_eq!(running.rms(), Some(RollingStats::rms(&*running).unwrap()));
+        let from_buffer = RollingRms::from_buffer(RollingBuffer::builder().size(2).prefill([9i16, 3, -4]).build().unwrap());
+        assert_eq!(from_buffer.rms(), Some(12.5f64.sqrt()));
+        running.clear();
+        assert_eq!(running.mean_square(), None);
+
+        let mut next = uniform(-1.0, 1.0);
+        let mut drifting = RollingRms::new(512);
+        let mut recomputed = RollingRms::new(512).recompute_every(512);
+        for _ in 0..1_000_000 {
+            let value = next();
+            drifting.push(value);
+            recomputed.push(value);
+        }
+        let exact = RollingStats::rms(&*drifting).unwrap();
+        assert!((drifting.rms().unwrap() - exact).abs() < 1e-9, "{:?} vs {exact}", drifting.rms());
+        assert!((recomputed.rms().unwrap() - exact).abs() < 1e-12, "{:?} vs {exact}", recomputed.rms());
+    }
+
+    #[test]
+    fn test_zero_crossings() {
+        use crate::buffer::stats::RollingStats;
+
+        let mut buffer = RollingBuffer::new(5);
+        assert_eq!((buffer.zero_crossings(), buffer.transitions(|_| true)), (0, 0));
+        buffer.push(-1.0);
+        assert_eq!(buffer.zero_crossings(), 0);
+        buffer.extend([9.0, 1.0, 2.0, 3.0, -1.0, -2.0]);
+        // logical [1, 2, 3, -1, -2]: the flip from 3 to -1 lands on the wrap boundary
+        assert_eq!(buffer.as_slices(), (&[1.0, 2.0, 3.0][..], &[-1.0, -2.0][..]));
+        assert_eq!(buffer.zero_crossings(), 1);
+        assert_eq!(buffer.transitions(|&value| value > 0.0), 1);
+        assert_eq!(buffer.transitions(|&value| value > 1.5), 2);
+        buffer.extend([0.0, 0.0, -3.0]);
+        assert_eq!(buffer.zero_crossings(), 0);
+        buffer.extend([0.0, 4.0]);
+        assert_eq!(buffer.zero_crossings(), 1);
+        assert_eq!(buffer.transitions(|&value| value == 0.0), 3);
+        buffer.extend([f64::NAN, -5.0]);
+        assert_eq!(buffer.zero_crossings(), 2);
+
+        let mut ints = RollingBuffer::new(0);
+        ints.extend([1i8, -1, 1, -1, 0, 0, 5, 0]);
+        assert_eq!(ints.zero_crossings(), 4);
+        let mut calls = 0;
+        ints.transitions(|_| {
+            calls += 1;
+            true
+        });
+        assert_eq!(calls, ints.len());
+    }
+
+    #[test]
+    fn test_as_slices_through_rolling_read() {
+        fn joined(buffer: &dyn RollingRead<i32>) -> Vec<i32> {
+            let (first, second) = buffer.as_slices();
+            [first, second].concat()
+        }
+        let mut buffer = RollingBuffer::<i32>::new(4);
+        let mut deque = RollingDeque::<i32>::new(4);
+        let mut array = RollingArray::<i32, 4>::default();
+        for i in 0..7 {
+            buffer.push(i);
+            deque.push(i);
+            array.push(i);
+            let expected = buffer.to_vec();
+            assert_eq!(joined(&buffer), expected);
+            assert_eq!(joined(&deque), expected);
+            assert_eq!(joined(&array), expected);
+        }
+        assert_eq!(RollingRead::as_slices(&buffer), (&[3][..], &[4, 5, 6][..]));
+    }
+
+    #[test]
+    fn test_new_in_global() {
+        use crate::buffer::allocator::Global;
+
+        let mut data: RollingBuffer<i32> = RollingBuffer::new_in(3, Global);
+        data.extend(1..=5);
+        assert_eq!((data.to_vec(), data.last_removed()), (vec![3, 4, 5], &Some(2)));
+        let _: &Global = data.allocator();
+    }
+
+    // Baseline code only imported `Rolling`, the reads have to work without `RollingRead` in scope.
+    mod rolling_only {
+        use crate::buffer::{buffer::RollingBuffer, traits::Rolling};
+
+        fn newest<B: Rolling<i32>>(buffer: &B) -> Option<&i32> {
+            buffer.last()
+        }
+
+        #[test]
+        fn test_reads_with_only_rolling_imported() {
+            let mut buffer = RollingBuffer::<i32>::new(3);
+            buffer.extend([1, 2, 3, 4]);
+            assert_eq!((buffer.get(3), buffer.get(0)), (Some(&4), None));
+            assert_eq!((buffer.first(), buffer.last(), buffer.len()), (Some(&2), Some(&4), 3));
+            assert_eq!(buffer.to_vec(), vec![2, 3, 4]);
+            assert_eq!(newest(&buffer), Some(&4));
+        }
+    }
+}